@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use snowid::{SnowID, SnowIDConfig};
+use snowid::{LocalSnowID, ShardedSnowID, SnowID, SnowIDConfig};
 
 pub fn node_bits_comparison(c: &mut Criterion) {
     let mut group = c.benchmark_group("Node Bits Comparison");
@@ -31,6 +31,22 @@ pub fn node_bits_comparison(c: &mut Criterion) {
     group.finish();
 }
 
+pub fn single_thread_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Single Thread");
+
+    group.bench_function("atomic", |b| {
+        let generator = SnowID::new(1).unwrap();
+        b.iter(|| black_box(generator.generate()));
+    });
+
+    group.bench_function("local", |b| {
+        let generator = LocalSnowID::new(1).unwrap();
+        b.iter(|| black_box(generator.generate()));
+    });
+
+    group.finish();
+}
+
 pub fn component_extraction_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("Component Extraction");
     let generator = SnowID::new(1).unwrap();
@@ -42,6 +58,33 @@ pub fn component_extraction_benchmarks(c: &mut Criterion) {
         });
     });
 
+    let ids: Vec<u64> = (0..10_000).map(|_| generator.generate()).collect();
+    let mut out = Vec::with_capacity(ids.len());
+
+    group.bench_function("decompose_slice_10k", |b| {
+        b.iter(|| {
+            generator.extract.decompose_slice(black_box(&ids), &mut out);
+            black_box(&out);
+        });
+    });
+
+    group.bench_function("decompose_columns_10k", |b| {
+        b.iter(|| {
+            black_box(generator.extract.decompose_columns(black_box(&ids)));
+        });
+    });
+
+    group.bench_function("map_collect_10k", |b| {
+        b.iter(|| {
+            black_box(
+                black_box(&ids)
+                    .iter()
+                    .map(|&id| generator.extract.decompose(id))
+                    .collect::<Vec<_>>(),
+            );
+        });
+    });
+
     group.finish();
 }
 
@@ -71,10 +114,51 @@ pub fn concurrent_benchmarks(c: &mut Criterion) {
     group.finish();
 }
 
+pub fn contended_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Contended");
+    const IDS_PER_THREAD: usize = 10_000;
+
+    for &thread_count in &[4, 8] {
+        group.bench_function(format!("shared/{}", thread_count), |b| {
+            let generator = SnowID::new(1).unwrap();
+            b.iter(|| {
+                std::thread::scope(|scope| {
+                    for _ in 0..thread_count {
+                        scope.spawn(|| {
+                            for _ in 0..IDS_PER_THREAD {
+                                black_box(generator.generate());
+                            }
+                        });
+                    }
+                });
+            });
+        });
+
+        group.bench_function(format!("sharded/{}", thread_count), |b| {
+            let generator = ShardedSnowID::new(1, SnowIDConfig::default(), 8).unwrap();
+            b.iter(|| {
+                std::thread::scope(|scope| {
+                    for _ in 0..thread_count {
+                        scope.spawn(|| {
+                            for _ in 0..IDS_PER_THREAD {
+                                black_box(generator.generate());
+                            }
+                        });
+                    }
+                });
+            });
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     node_bits_comparison,
+    single_thread_benchmarks,
     concurrent_benchmarks,
+    contended_benchmarks,
     component_extraction_benchmarks
 );
 criterion_main!(benches);
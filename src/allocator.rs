@@ -0,0 +1,94 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Lock-free pool of distinct node IDs for generators within one process
+///
+/// IDs in `[0, max_node]` are tracked in an atomic bitset and handed out
+/// round-robin, so a released ID is not immediately reissued to the next
+/// caller.
+#[derive(Debug)]
+pub struct NodeIdAllocator {
+    used: Box<[AtomicU64]>,
+    max_node: u16,
+    next: AtomicUsize,
+}
+
+impl NodeIdAllocator {
+    /// Create a pool of the node IDs `0..=max_node`
+    ///
+    /// # Arguments
+    /// * `max_node` - Highest node ID in the pool
+    pub fn new(max_node: u16) -> Self {
+        let words = max_node as usize / 64 + 1;
+        Self {
+            used: (0..words).map(|_| AtomicU64::new(0)).collect(),
+            max_node,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Claim a node ID that no other caller currently holds
+    ///
+    /// # Returns
+    /// * `Option<u16>` - Claimed node ID, or `None` if every ID in the pool is taken
+    pub fn allocate(&self) -> Option<u16> {
+        let count = self.max_node as usize + 1;
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % count;
+
+        (0..count)
+            .map(|offset| (start + offset) % count)
+            .find_map(|id| {
+                let bit = 1u64 << (id % 64);
+                let previous = self.used[id / 64].fetch_or(bit, Ordering::AcqRel);
+                (previous & bit == 0).then_some(id as u16)
+            })
+    }
+
+    /// Return a node ID to the pool
+    ///
+    /// Only release an ID once no generator uses it any more. IDs outside the
+    /// pool are ignored.
+    ///
+    /// # Arguments
+    /// * `id` - Node ID previously returned by `allocate`
+    pub fn release(&self, id: u16) {
+        if id <= self.max_node {
+            let bit = 1u64 << (id % 64);
+            self.used[id as usize / 64].fetch_and(!bit, Ordering::AcqRel);
+        }
+    }
+
+    /// Get the highest node ID in the pool
+    #[inline]
+    pub fn max_node(&self) -> u16 {
+        self.max_node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_allocate_until_exhausted() {
+        let allocator = NodeIdAllocator::new(99);
+
+        let ids: HashSet<u16> = (0..100).map(|_| allocator.allocate().unwrap()).collect();
+        assert_eq!(ids, (0..=99).collect());
+        assert_eq!(allocator.allocate(), None);
+
+        allocator.release(42);
+        allocator.release(500);
+        assert_eq!(allocator.allocate(), Some(42));
+        assert_eq!(allocator.allocate(), None);
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let allocator = NodeIdAllocator::new(3);
+
+        let first = allocator.allocate().unwrap();
+        allocator.release(first);
+        assert_ne!(allocator.allocate(), Some(first));
+    }
+}
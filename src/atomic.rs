@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Lock-free high-water mark of the highest SnowID seen so far
+///
+/// SnowIDs grow with time, so tracking the latest processed ID across threads
+/// only ever needs to move the stored value forward.
+#[derive(Debug, Default)]
+pub struct AtomicSnowID {
+    value: AtomicU64,
+}
+
+impl AtomicSnowID {
+    /// Create a new high-water mark starting at `id`
+    ///
+    /// # Arguments
+    /// * `id` - Initial SnowID value
+    pub const fn new(id: u64) -> Self {
+        Self {
+            value: AtomicU64::new(id),
+        }
+    }
+
+    /// Get the highest SnowID seen so far
+    #[inline]
+    pub fn load(&self) -> u64 {
+        self.value.load(Ordering::Acquire)
+    }
+
+    /// Raise the high-water mark to `id` if it is greater than the current value
+    ///
+    /// # Arguments
+    /// * `id` - SnowID that was just processed
+    ///
+    /// # Returns
+    /// * `bool` - `true` if the stored value advanced to `id`
+    #[inline]
+    pub fn fetch_max(&self, id: u64) -> bool {
+        self.value.fetch_max(id, Ordering::AcqRel) < id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch_max_only_advances() {
+        let latest = AtomicSnowID::new(10);
+
+        assert!(latest.fetch_max(20));
+        assert!(!latest.fetch_max(20));
+        assert!(!latest.fetch_max(15));
+        assert_eq!(latest.load(), 20);
+        assert_eq!(AtomicSnowID::default().load(), 0);
+    }
+}
@@ -0,0 +1,83 @@
+use std::cmp::Ordering;
+
+use crate::{SnowIDConfig, SnowIDExtractor};
+
+/// Orders SnowIDs from two configurations by creation time
+///
+/// Raw integer comparison is meaningless when the configurations use different
+/// epochs or layouts. This compares the absolute Unix time of each ID under its
+/// own configuration instead, breaking ties by sequence number, which allows
+/// merge-sorting heterogeneous ID streams.
+#[derive(Debug, Copy, Clone)]
+pub struct CrossConfigComparator {
+    first: SnowIDExtractor,
+    first_epoch: u64,
+    second: SnowIDExtractor,
+    second_epoch: u64,
+}
+
+impl CrossConfigComparator {
+    /// Create a comparator for IDs from `first` against IDs from `second`
+    ///
+    /// # Arguments
+    /// * `first` - Configuration of the left-hand IDs
+    /// * `second` - Configuration of the right-hand IDs
+    pub fn new(first: SnowIDConfig, second: SnowIDConfig) -> Self {
+        Self {
+            first: SnowIDExtractor::new(first),
+            first_epoch: first.epoch(),
+            second: SnowIDExtractor::new(second),
+            second_epoch: second.epoch(),
+        }
+    }
+
+    /// Compare an ID from the first configuration with one from the second
+    ///
+    /// # Arguments
+    /// * `a` - SnowID created under the first configuration
+    /// * `b` - SnowID created under the second configuration
+    ///
+    /// # Returns
+    /// * `Ordering` - Order of `a` relative to `b` by Unix time, then sequence
+    pub fn cmp(&self, a: u64, b: u64) -> Ordering {
+        let (ts_a, _, seq_a) = self.first.decompose(a);
+        let (ts_b, _, seq_b) = self.second.decompose(b);
+
+        (ts_a + self.first_epoch, seq_a).cmp(&(ts_b + self.second_epoch, seq_b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SnowID;
+
+    #[test]
+    fn test_cmp_across_epochs() {
+        let old = SnowIDConfig::builder().epoch(1_600_000_000_000).build();
+        let new = SnowIDConfig::builder()
+            .epoch(1_700_000_000_000)
+            .node_bits(16)
+            .build();
+        let old_gen = SnowID::with_config(1, old).unwrap();
+        let new_gen = SnowID::with_config(1, new).unwrap();
+        let comparator = CrossConfigComparator::new(old, new);
+
+        let time = 1_750_000_000_000;
+        let a = old_gen.generate_with_time(time).unwrap();
+        let b = new_gen.generate_with_time(time + 1).unwrap();
+
+        // The older epoch gives the earlier ID a larger integer value
+        assert!(a > b);
+        assert_eq!(comparator.cmp(a, b), Ordering::Less);
+
+        let later = old_gen.generate_with_time(time + 2).unwrap();
+        assert_eq!(comparator.cmp(later, b), Ordering::Greater);
+
+        // Same millisecond falls back to the sequence
+        let same = new_gen.generate_with_time(time + 2).unwrap();
+        assert_eq!(comparator.cmp(later, same), Ordering::Equal);
+        let again = old_gen.generate_with_time(time + 2).unwrap();
+        assert_eq!(comparator.cmp(again, same), Ordering::Greater);
+    }
+}
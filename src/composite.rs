@@ -0,0 +1,144 @@
+use crate::SnowIDError;
+
+/// Packs a parent SnowID and a small child counter into one `u128` key
+///
+/// The layout, from the most significant bit, is:
+///
+/// * `64 - child_bits` unused bits, always zero
+/// * 64 bits holding the full parent SnowID
+/// * `child_bits` bits holding the child counter
+///
+/// Keys therefore sort by parent first and child second, and fit in
+/// `64 + child_bits` bits, e.g. a 10-byte column for a 16-bit child. This is
+/// a composite of two values, not a 128-bit SnowID layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CompositeKey {
+    child_bits: u8,
+}
+
+impl CompositeKey {
+    /// Create a composite key layout with a child field of the given width
+    ///
+    /// # Arguments
+    /// * `child_bits` - Width of the child counter, between 1 and 64
+    ///
+    /// # Returns
+    /// * `Result<CompositeKey, SnowIDError>` - Layout or error if the width is out of range
+    pub fn new(child_bits: u8) -> Result<Self, SnowIDError> {
+        if !(1..=64).contains(&child_bits) {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "child must be between 1 and 64 bits",
+            });
+        }
+
+        Ok(Self { child_bits })
+    }
+
+    /// Get the width of the child field
+    #[inline]
+    pub fn child_bits(&self) -> u8 {
+        self.child_bits
+    }
+
+    /// Get the largest child counter that fits the child field
+    #[inline]
+    pub fn max_child(&self) -> u64 {
+        u64::MAX >> (64 - self.child_bits)
+    }
+
+    /// Pack a parent SnowID and a child counter into one key
+    ///
+    /// # Arguments
+    /// * `parent` - Full parent SnowID
+    /// * `child` - Child counter
+    ///
+    /// # Returns
+    /// * `Result<u128, SnowIDError>` - Composite key or error if the child does not fit
+    pub fn pack(&self, parent: u64, child: u64) -> Result<u128, SnowIDError> {
+        if child > self.max_child() {
+            return Err(SnowIDError::ComponentOutOfRange {
+                component: "child",
+                value: child,
+                max: self.max_child(),
+            });
+        }
+
+        Ok(((parent as u128) << self.child_bits) | child as u128)
+    }
+
+    /// Get the parent SnowID of a composite key
+    #[inline]
+    pub fn parent(&self, key: u128) -> u64 {
+        (key >> self.child_bits) as u64
+    }
+
+    /// Get the child counter of a composite key
+    #[inline]
+    pub fn child(&self, key: u128) -> u64 {
+        key as u64 & self.max_child()
+    }
+
+    /// Split a composite key into its parent SnowID and child counter
+    ///
+    /// # Arguments
+    /// * `key` - Key produced by `pack`
+    ///
+    /// # Returns
+    /// * `(u64, u64)` - Parent SnowID and child counter
+    pub fn unpack(&self, key: u128) -> (u64, u64) {
+        (self.parent(key), self.child(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SnowID;
+
+    #[test]
+    fn test_round_trip() {
+        let parent = SnowID::new(7).unwrap().generate();
+
+        for child_bits in [1, 16, 63, 64] {
+            let layout = CompositeKey::new(child_bits).unwrap();
+            for child in [0, 1, layout.max_child()] {
+                let key = layout.pack(parent, child).unwrap();
+                assert_eq!(layout.unpack(key), (parent, child));
+                assert!(key.leading_zeros() >= 64 - child_bits as u32);
+            }
+        }
+
+        let layout = CompositeKey::new(16).unwrap();
+        let key = layout.pack(u64::MAX, 0xFFFF).unwrap();
+        assert_eq!(layout.unpack(key), (u64::MAX, 0xFFFF));
+    }
+
+    #[test]
+    fn test_sorts_by_parent_then_child() {
+        let layout = CompositeKey::new(8).unwrap();
+
+        let a = layout.pack(100, 255).unwrap();
+        let b = layout.pack(101, 0).unwrap();
+        let c = layout.pack(101, 1).unwrap();
+        assert!(a < b && b < c);
+    }
+
+    #[test]
+    fn test_invalid_widths() {
+        assert!(matches!(
+            CompositeKey::new(0),
+            Err(SnowIDError::InvalidLayout { .. })
+        ));
+        assert!(CompositeKey::new(65).is_err());
+
+        let layout = CompositeKey::new(4).unwrap();
+        assert_eq!(
+            layout.pack(1, 16),
+            Err(SnowIDError::ComponentOutOfRange {
+                component: "child",
+                value: 16,
+                max: 15,
+            })
+        );
+    }
+}
@@ -1,14 +1,37 @@
-use crate::SnowID;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{encoding, BitConfig, SnowID, SnowIDError, SnowIDExtractor};
 
 /// Default configuration values
 const DEFAULT_NODE_BITS: u8 = 10;
 const DEFAULT_CUSTOM_EPOCH: u64 = 1704067200000; // January 1, 2024 UTC
+/// Most timestamp bits that can be reserved, leaving 36 bits (about 2 years)
+const MAX_RESERVED_BITS: u8 = 6;
+
+/// Behavior when the sequence is exhausted within a millisecond
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SequencePolicy {
+    /// Sleep with exponential backoff, then wait for the next millisecond
+    #[default]
+    RollToNextMs,
+    /// Busy-wait (yielding) for the next millisecond without sleeping;
+    /// lowest latency at the cost of CPU time
+    BlockUntilNextMs,
+    /// Fail `try_generate` with `SnowIDError::SequenceExhausted`
+    Error,
+}
 
 /// Configuration for SnowID generator
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SnowIDConfig {
     node_bits: u8,
+    reserved_bits: u8,
     custom_epoch: u64,
+    sequence_policy: SequencePolicy,
+    sequence_reset_base: u16,
     timestamp_shift: u8,
     node_shift: u8,
     timestamp_mask: u64,
@@ -20,28 +43,163 @@ impl SnowIDConfig {
     /// Create new SnowIDConfig with given node bits
     fn new(node_bits: u8, custom_epoch: u64) -> Self {
         let sequence_bits = SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - node_bits;
+        let bits = BitConfig::for_layout(SnowID::TIMESTAMP_BITS as u8, node_bits, sequence_bits);
         Self {
             node_bits,
+            reserved_bits: 0,
             custom_epoch,
-            timestamp_shift: SnowID::TOTAL_NODE_AND_SEQUENCE_BITS,
-            node_shift: sequence_bits,
-            timestamp_mask: (1 << SnowID::TIMESTAMP_BITS) - 1,
-            node_mask: ((1u32 << node_bits) - 1) as u16,
-            sequence_mask: ((1u32 << sequence_bits) - 1) as u16,
+            sequence_policy: SequencePolicy::default(),
+            sequence_reset_base: 0,
+            timestamp_shift: bits.timestamp_shift,
+            node_shift: bits.node_shift,
+            timestamp_mask: bits.timestamp_mask,
+            node_mask: bits.node_mask,
+            sequence_mask: bits.sequence_mask,
         }
     }
 
+    /// Carve `reserved_bits` off the top of the timestamp field
+    fn with_reserved_bits(mut self, reserved_bits: u8) -> Self {
+        self.reserved_bits = reserved_bits;
+        self.timestamp_mask >>= reserved_bits;
+        self
+    }
+
     /// Create a new configuration builder
     pub fn builder() -> SnowIDConfigBuilder {
         SnowIDConfigBuilder::new()
     }
 
+    /// Best-effort inference of the configuration that produced a set of SnowIDs
+    ///
+    /// Assumes all samples come from a single generator. Low bits that vary
+    /// between samples are taken as sequence, bits that stay set are taken as
+    /// node ID, and a layout is only returned when exactly one valid node bit
+    /// count is consistent with both. The epoch is the default one if it puts
+    /// every sample in the past; otherwise the newest sample is assumed to have
+    /// been created just now.
+    ///
+    /// # Arguments
+    /// * `ids` - Sample of SnowIDs from a single generator
+    ///
+    /// # Returns
+    /// * `Option<SnowIDConfig>` - Likely configuration, or `None` when the samples are
+    ///   empty or ambiguous (e.g. node ID 0, or sequences that never reach their upper bits)
+    pub fn infer_from_samples(ids: &[u64]) -> Option<SnowIDConfig> {
+        let (&first, rest) = ids.split_first()?;
+        let total_bits = SnowID::TOTAL_NODE_AND_SEQUENCE_BITS;
+        let low_mask = (1u64 << total_bits) - 1;
+
+        let varying = rest.iter().fold(0, |acc, &id| acc | (id ^ first)) & low_mask;
+        let constant_set = first & !varying & low_mask;
+
+        // The sequence field must cover every varying bit and end below the
+        // lowest bit that is always set, which has to belong to the node ID
+        let min_sequence_bits = (u64::BITS - varying.leading_zeros()) as u8;
+        let max_sequence_bits = match constant_set {
+            0 => total_bits,
+            set => set.trailing_zeros() as u8,
+        };
+
+        let lowest = min_sequence_bits.max(total_bits - 16);
+        let highest = max_sequence_bits.min(total_bits - 6);
+        if lowest != highest {
+            return None;
+        }
+
+        let config = Self::new(total_bits - lowest, DEFAULT_CUSTOM_EPOCH);
+        let newest = ids.iter().map(|&id| id >> total_bits).max()?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_millis() as u64;
+        let epoch = if DEFAULT_CUSTOM_EPOCH + newest <= now {
+            DEFAULT_CUSTOM_EPOCH
+        } else {
+            config.plausible_epoch_for(ids)?
+        };
+
+        Some(Self::new(total_bits - lowest, epoch))
+    }
+
+    /// Estimate the epoch a set of SnowIDs was generated with under this layout
+    ///
+    /// Assumes the newest ID was created just now, so the estimate is only as
+    /// good as that assumption: it is late by however long ago the newest ID was
+    /// generated. Useful for spotting datasets produced with different epochs.
+    ///
+    /// # Arguments
+    /// * `ids` - SnowIDs generated with this bit layout
+    ///
+    /// # Returns
+    /// * `Option<u64>` - Estimated epoch in milliseconds since Unix epoch, or `None`
+    ///   when `ids` is empty or the newest ID would lie before the Unix epoch
+    pub fn plausible_epoch_for(&self, ids: &[u64]) -> Option<u64> {
+        let newest = ids.iter().map(|&id| id >> self.timestamp_shift).max()?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_millis() as u64;
+
+        now.checked_sub(newest)
+    }
+
+    /// Heuristically check whether a SnowID lost its high bits in a narrower column
+    ///
+    /// An ID generated now needs the bit length of the current timestamp plus
+    /// `timestamp_shift` bits, and that width grows by one each time the time
+    /// since the epoch doubles. If `column_bits` is narrower than that and the
+    /// ID fits in it, the ID was most likely cut down to the column width.
+    /// This is a best-effort diagnosis: an ID created shortly after the epoch
+    /// is legitimately narrow, and a truncated ID whose remaining bits look
+    /// like a current ID is not detected.
+    ///
+    /// # Arguments
+    /// * `id` - SnowID read back from storage
+    /// * `column_bits` - Width of the integer column it was stored in, e.g. 32
+    ///
+    /// # Returns
+    /// * `bool` - `true` if the ID looks truncated to `column_bits`
+    pub fn is_truncated(&self, id: u64, column_bits: u8) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_millis() as u64);
+        self.is_truncated_at(id, column_bits, now.saturating_sub(self.custom_epoch))
+    }
+
+    /// Truncation check against the width of an ID generated at `timestamp`
+    fn is_truncated_at(&self, id: u64, column_bits: u8, timestamp: u64) -> bool {
+        let expected_bits =
+            64 - ((timestamp & self.timestamp_mask) << self.timestamp_shift).leading_zeros();
+        let id_bits = 64 - id.leading_zeros();
+
+        (column_bits as u32) < expected_bits && id_bits <= column_bits as u32
+    }
+
     /// Get epoch timestamp
     #[inline]
     pub fn epoch(&self) -> u64 {
         self.custom_epoch
     }
 
+    /// Get epoch as a `SystemTime`, e.g. for display or logging
+    #[inline]
+    pub fn epoch_systemtime(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.custom_epoch)
+    }
+
+    /// Get timestamp bits configuration, 42 minus any reserved high bits
+    #[inline]
+    pub fn timestamp_bits(&self) -> u8 {
+        SnowID::TIMESTAMP_BITS as u8 - self.reserved_bits
+    }
+
+    /// Get the number of high bits reserved for future layout changes
+    #[inline]
+    pub fn reserved_high_bits(&self) -> u8 {
+        self.reserved_bits
+    }
+
     /// Get node bits configuration
     #[inline]
     pub fn node_bits(&self) -> u8 {
@@ -54,18 +212,313 @@ pub fn sequence_bits(&self) -> u8 {
         SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - self.node_bits
     }
 
+    /// Get the policy applied when the sequence is exhausted
+    #[inline]
+    pub fn sequence_policy(&self) -> SequencePolicy {
+        self.sequence_policy
+    }
+
+    /// Get the sequence number each millisecond starts at
+    #[inline]
+    pub fn sequence_reset_base(&self) -> u16 {
+        self.sequence_reset_base
+    }
+
     /// Get the maximum node ID supported by the current configuration
     #[inline]
     pub fn max_node_id(&self) -> u16 {
         self.node_mask
     }
 
+    /// Get the number of distinct node IDs supported by the current configuration
+    ///
+    /// Returned as `u32` since `max_node_id() + 1` overflows `u16` at 16 node bits.
+    #[inline]
+    pub fn max_nodes(&self) -> u32 {
+        1 << self.node_bits
+    }
+
+    /// Find the smallest node bit width that supports a number of nodes
+    ///
+    /// Widths below the 6-bit minimum are never returned, so small counts
+    /// yield 6.
+    ///
+    /// # Arguments
+    /// * `node_count` - Number of distinct node IDs required
+    ///
+    /// # Returns
+    /// * `Option<u8>` - Node bits (6-16), or `None` if more than 65,536 nodes are required
+    pub fn min_node_bits_for(node_count: u32) -> Option<u8> {
+        if node_count > 1 << 16 {
+            return None;
+        }
+        Some(node_count.next_power_of_two().trailing_zeros().max(6) as u8)
+    }
+
+    /// Get the total number of distinct IDs the layout can represent
+    ///
+    /// Computed as `2^(timestamp_bits + node_bits + sequence_bits)`, returned as
+    /// `u128` because the full 64-bit layout holds `2^64` values. Reserved high
+    /// bits are always zero and do not count.
+    #[inline]
+    pub fn total_id_space(&self) -> u128 {
+        1u128
+            << (self.timestamp_bits() as u32 + self.node_bits as u32 + self.sequence_bits() as u32)
+    }
+
+    /// Probability that at least two instances pick the same node ID
+    ///
+    /// Assumes node IDs are assigned uniformly at random, e.g. by hashing
+    /// hostnames, and computes the exact birthday-problem probability.
+    ///
+    /// # Arguments
+    /// * `num_instances` - Number of instances deriving their node ID independently
+    ///
+    /// # Returns
+    /// * `f64` - Collision probability between 0.0 and 1.0
+    pub fn birthday_collision_probability(&self, num_instances: usize) -> f64 {
+        let nodes = self.max_nodes() as usize;
+        if num_instances > nodes {
+            return 1.0;
+        }
+
+        let no_collision = (0..num_instances)
+            .map(|taken| 1.0 - taken as f64 / nodes as f64)
+            .product::<f64>();
+
+        1.0 - no_collision
+    }
+
     /// Get the maximum sequence number supported by the current configuration
     #[inline]
     pub fn max_sequence_id(&self) -> u16 {
         self.sequence_mask
     }
 
+    /// Count how many more IDs fit before the next whole second
+    ///
+    /// Adds the sequence values left after `sequence` in the current
+    /// millisecond to the full capacity of the milliseconds left in the current
+    /// Unix second, taking the sequence reset base into account.
+    ///
+    /// # Arguments
+    /// * `timestamp` - Current timestamp in milliseconds since the configured epoch
+    /// * `sequence` - Last sequence number used in that millisecond
+    ///
+    /// # Returns
+    /// * `u64` - Number of IDs a single generator can still issue this second
+    pub fn ids_per_second_remaining(&self, timestamp: u64, sequence: u16) -> u64 {
+        let per_ms = (self.max_sequence_id() - self.sequence_reset_base) as u64 + 1;
+        let ms_left = 999 - (self.custom_epoch + timestamp) % 1000;
+
+        self.max_sequence_id().saturating_sub(sequence) as u64 + ms_left * per_ms
+    }
+
+    /// Render the bit layout as an ASCII diagram
+    ///
+    /// One letter per bit from most to least significant, e.g. for the default
+    /// layout `[T x 42][N x 10][S x 12]`, which is handy in logs and docs.
+    ///
+    /// # Returns
+    /// * `String` - Bracketed groups of `T`, `N` and `S` covering all 64 bits
+    pub fn bit_diagram(&self) -> String {
+        self.bit_fields()
+            .iter()
+            .filter(|&&(_, bits)| bits > 0)
+            .map(|&(symbol, bits)| format!("[{}]", symbol.to_string().repeat(bits)))
+            .collect()
+    }
+
+    /// Diagram symbol and width of each field, from most to least significant
+    pub(crate) fn bit_fields(&self) -> [(char, usize); 4] {
+        let fields = [
+            ('R', self.reserved_bits as usize),
+            ('T', self.timestamp_bits() as usize),
+            ('N', self.node_bits() as usize),
+            ('S', self.sequence_bits() as usize),
+        ];
+        debug_assert_eq!(fields.iter().map(|&(_, bits)| bits).sum::<usize>(), 64);
+        fields
+    }
+
+    /// Build a full SnowID from a value that stored only timestamp and sequence
+    ///
+    /// For migrating data where the node ID lived in a separate column and the
+    /// stored integer was `timestamp << sequence_bits | sequence`. Timestamp bits
+    /// beyond the timestamp field are discarded, as in generated IDs.
+    ///
+    /// # Arguments
+    /// * `timestamp_sequence_value` - Stored value without node bits
+    /// * `node` - Node ID from the separate column
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - Full SnowID or error if the node ID does not fit
+    pub fn compose_with_external_node(
+        &self,
+        timestamp_sequence_value: u64,
+        node: u16,
+    ) -> Result<u64, SnowIDError> {
+        if node > self.max_node_id() {
+            return Err(SnowIDError::InvalidNodeId {
+                node_id: node,
+                max: self.max_node_id(),
+            });
+        }
+
+        let timestamp = (timestamp_sequence_value >> self.sequence_bits()) & self.timestamp_mask;
+        let sequence = timestamp_sequence_value & self.sequence_mask as u64;
+
+        Ok((timestamp << self.timestamp_shift) | ((node as u64) << self.node_shift) | sequence)
+    }
+
+    /// Split a SnowID into a timestamp-and-sequence value and its node ID
+    ///
+    /// Inverse of `compose_with_external_node`.
+    ///
+    /// # Arguments
+    /// * `id` - SnowID to split
+    ///
+    /// # Returns
+    /// * `(u64, u16)` - Value without node bits, and the node ID
+    pub fn split_external_node(&self, id: u64) -> (u64, u16) {
+        let extract = SnowIDExtractor::new(*self);
+        let timestamp = id >> self.timestamp_shift;
+        let sequence = extract.sequence(id) as u64;
+
+        (
+            (timestamp << self.sequence_bits()) | sequence,
+            extract.node(id),
+        )
+    }
+
+    /// Check whether every SnowID of another configuration fits this one
+    ///
+    /// True when the node and sequence fields are at least as wide as in
+    /// `other`, and the whole timestamp range of `other`, shifted onto this
+    /// epoch, lies within this timestamp range. Because node and sequence bits
+    /// always add up to 22, the node widths must match exactly.
+    ///
+    /// # Arguments
+    /// * `other` - Configuration the IDs were produced under
+    ///
+    /// # Returns
+    /// * `bool` - `true` if re-laying out IDs of `other` under this configuration
+    ///   never truncates a field
+    pub fn can_contain(&self, other: &SnowIDConfig) -> bool {
+        let Some(epoch_offset) = other.custom_epoch.checked_sub(self.custom_epoch) else {
+            return false;
+        };
+
+        self.node_bits >= other.node_bits
+            && self.sequence_bits() >= other.sequence_bits()
+            && epoch_offset
+                .checked_add(other.timestamp_mask)
+                .is_some_and(|newest| newest <= self.timestamp_mask)
+    }
+
+    /// Clear the node field of a SnowID for deduplication
+    ///
+    /// After a node ID migration the same logical event can be stored under
+    /// IDs that differ only in their node bits. Their timestamp and sequence
+    /// still match, so the keys collapse to one value; note that genuinely
+    /// different events from two nodes collide the same way.
+    ///
+    /// # Arguments
+    /// * `id` - SnowID to reduce
+    ///
+    /// # Returns
+    /// * `u64` - The ID with its node field zeroed
+    #[inline]
+    pub fn dedup_key(&self, id: u64) -> u64 {
+        id & !((self.node_mask as u64) << self.node_shift)
+    }
+
+    /// Convert a SnowID into a key that sorts newest millisecond first
+    ///
+    /// Only the timestamp field is complemented, so keys of different
+    /// milliseconds sort in descending time order, while keys within one
+    /// millisecond keep ascending node and sequence order. This differs from
+    /// `SnowID::generate_descending`, which complements the whole value and
+    /// therefore also reverses the sequence.
+    ///
+    /// # Arguments
+    /// * `id` - SnowID to convert
+    ///
+    /// # Returns
+    /// * `u64` - Time-descending key
+    #[inline]
+    pub fn to_time_descending_key(&self, id: u64) -> u64 {
+        id ^ (self.timestamp_mask << self.timestamp_shift)
+    }
+
+    /// Convert a key produced by `to_time_descending_key` back into a SnowID
+    ///
+    /// # Arguments
+    /// * `key` - Time-descending key
+    ///
+    /// # Returns
+    /// * `u64` - Original SnowID
+    #[inline]
+    pub fn from_time_descending_key(&self, key: u64) -> u64 {
+        key ^ (self.timestamp_mask << self.timestamp_shift)
+    }
+
+    /// Derive a SnowID-shaped value from event content for idempotent ingestion
+    ///
+    /// The timestamp field holds the given timestamp, and the node and sequence
+    /// fields hold a Kafka murmur2 hash of `content`, so the same content at the
+    /// same timestamp always yields the same ID and keeps time ordering. This
+    /// gives up the uniqueness guarantee: different content can hash to the same
+    /// 22 bits, and derived IDs may collide with generated ones.
+    ///
+    /// # Arguments
+    /// * `timestamp` - Milliseconds since the epoch, e.g. the event time
+    /// * `content` - Event content to hash
+    ///
+    /// # Returns
+    /// * `u64` - Deterministic ID; timestamp bits beyond the timestamp field are discarded
+    pub fn derive_from_hash(&self, timestamp: u64, content: &[u8]) -> u64 {
+        let hash = encoding::murmur2(content) as u32 as u64;
+
+        ((timestamp & self.timestamp_mask) << self.timestamp_shift)
+            | (hash & ((1 << self.timestamp_shift) - 1))
+    }
+
+    /// Build canonical test vectors for checking other implementations
+    ///
+    /// Every combination of a few timestamps, node IDs and sequence numbers,
+    /// covering zero, one, an alternating bit pattern and the maximum of each
+    /// field, together with the packed SnowID and its Crockford base32
+    /// encoding. The set and its order depend only on the layout, so ports to
+    /// other languages can check bit compatibility against it.
+    ///
+    /// # Returns
+    /// * `Vec<(u64, u16, u16, u64, String)>` - Timestamp since the epoch, node ID,
+    ///   sequence, packed SnowID and its encoding
+    pub fn test_vectors(&self) -> Vec<(u64, u16, u16, u64, String)> {
+        let timestamps = [
+            0,
+            1,
+            0xAAAA_AAAA_AAAA & self.timestamp_mask,
+            self.timestamp_mask,
+        ];
+        let nodes = [0, 1, 0xAAAA & self.node_mask, self.node_mask];
+        let sequences = [0, 1, 0x5555 & self.sequence_mask, self.sequence_mask];
+
+        let mut vectors = Vec::with_capacity(timestamps.len() * nodes.len() * sequences.len());
+        for timestamp in timestamps {
+            for node in nodes {
+                for sequence in sequences {
+                    let id = (timestamp << self.timestamp_shift)
+                        | ((node as u64) << self.node_shift)
+                        | sequence as u64;
+                    vectors.push((timestamp, node, sequence, id, encoding::encode(id)));
+                }
+            }
+        }
+        vectors
+    }
+
     // Internal methods used by SnowID and SnowIDExtractor
     #[inline]
     pub(crate) fn timestamp_shift(&self) -> u8 {
@@ -82,6 +535,26 @@ pub(crate) fn timestamp_mask(&self) -> u64 {
         self.timestamp_mask
     }
 
+    /// Check that the current time still fits the timestamp field
+    ///
+    /// Reserved high bits shrink the field, so a layout can run out of range
+    /// long before the full 42 bits would.
+    pub(crate) fn check_clock_in_range(&self) -> Result<(), SnowIDError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64);
+        let timestamp = now.saturating_sub(self.custom_epoch);
+
+        if timestamp > self.timestamp_mask {
+            return Err(SnowIDError::ComponentOutOfRange {
+                component: "timestamp",
+                value: timestamp,
+                max: self.timestamp_mask,
+            });
+        }
+        Ok(())
+    }
+
     #[inline]
     pub(crate) fn node_mask(&self) -> u16 {
         self.node_mask
@@ -103,7 +576,10 @@ fn default() -> Self {
 #[derive(Debug)]
 pub struct SnowIDConfigBuilder {
     node_bits: u8,
+    reserved_bits: u8,
     custom_epoch: u64,
+    sequence_policy: SequencePolicy,
+    sequence_reset_base: u16,
 }
 
 impl SnowIDConfigBuilder {
@@ -111,7 +587,10 @@ impl SnowIDConfigBuilder {
     pub fn new() -> Self {
         Self {
             node_bits: DEFAULT_NODE_BITS,
+            reserved_bits: 0,
             custom_epoch: DEFAULT_CUSTOM_EPOCH,
+            sequence_policy: SequencePolicy::default(),
+            sequence_reset_base: 0,
         }
     }
 
@@ -140,6 +619,22 @@ pub fn node_bits(mut self, bits: u8) -> Self {
         self
     }
 
+    /// Set the node bits to the smallest width supporting `count` nodes
+    ///
+    /// # Arguments
+    /// * `count` - Number of distinct node IDs required
+    ///
+    /// # Returns
+    /// * `Self` - Builder instance for chaining
+    ///
+    /// # Panics
+    /// Panics if more than 65,536 nodes are required
+    pub fn node_count(self, count: u32) -> Self {
+        let bits =
+            SnowIDConfig::min_node_bits_for(count).expect("Node count must not exceed 65536");
+        self.node_bits(bits)
+    }
+
     /// Set a custom epoch timestamp in milliseconds
     ///
     /// # Arguments
@@ -152,12 +647,80 @@ pub fn epoch(mut self, epoch: u64) -> Self {
         self
     }
 
+    /// Reserve the most significant bits of the timestamp for future use
+    ///
+    /// Reserved bits are always zero in generated and composed IDs, leaving
+    /// migration headroom, e.g. to widen the node or sequence field later
+    /// without clashing with IDs already issued. Each reserved bit halves how
+    /// long the timestamp lasts: 2 bits leave about 35 years after the epoch.
+    /// Generators reject a layout whose range the clock has already passed,
+    /// and panic instead of wrapping once the clock passes it later.
+    ///
+    /// # Arguments
+    /// * `bits` - Number of high bits to reserve (0-6)
+    ///
+    /// # Returns
+    /// * `Self` - Builder instance for chaining
+    ///
+    /// # Panics
+    /// Panics if more than 6 bits are reserved
+    pub fn reserved_high_bits(mut self, bits: u8) -> Self {
+        assert!(
+            bits <= MAX_RESERVED_BITS,
+            "Reserved bits must be between 0 and 6"
+        );
+        self.reserved_bits = bits;
+        self
+    }
+
+    /// Set the behavior when the sequence is exhausted within a millisecond
+    ///
+    /// # Arguments
+    /// * `policy` - Sequence exhaustion policy (default: `SequencePolicy::RollToNextMs`)
+    ///
+    /// # Returns
+    /// * `Self` - Builder instance for chaining
+    pub fn sequence_policy(mut self, policy: SequencePolicy) -> Self {
+        self.sequence_policy = policy;
+        self
+    }
+
+    /// Start the sequence of every millisecond at `base` instead of 0
+    ///
+    /// Leaves the sequence values below `base` free, e.g. for special IDs
+    /// composed by hand. Each millisecond then only holds
+    /// `max_sequence_id() + 1 - base` generated IDs.
+    ///
+    /// # Arguments
+    /// * `base` - First sequence number of each millisecond (default: 0)
+    ///
+    /// # Returns
+    /// * `Self` - Builder instance for chaining
+    ///
+    /// # Panics
+    /// `build` panics if `base` is not below the maximum sequence number
+    pub fn sequence_reset_base(mut self, base: u16) -> Self {
+        self.sequence_reset_base = base;
+        self
+    }
+
     /// Build the final SnowIDConfig
     ///
     /// # Returns
     /// * `SnowIDConfig` - The configured SnowIDConfig instance
+    ///
+    /// # Panics
+    /// Panics if the sequence reset base is not below the maximum sequence number
     pub fn build(self) -> SnowIDConfig {
-        SnowIDConfig::new(self.node_bits, self.custom_epoch)
+        let mut config = SnowIDConfig::new(self.node_bits, self.custom_epoch)
+            .with_reserved_bits(self.reserved_bits);
+        assert!(
+            self.sequence_reset_base < config.max_sequence_id(),
+            "Sequence reset base must be below the maximum sequence number"
+        );
+        config.sequence_policy = self.sequence_policy;
+        config.sequence_reset_base = self.sequence_reset_base;
+        config
     }
 }
 
@@ -167,9 +730,74 @@ fn default() -> Self {
     }
 }
 
+/// Compact, serializable description of a SnowID bit layout
+///
+/// Lets a producer advertise its layout so consumers can reconstruct a
+/// matching `SnowIDConfig` for decoding. A `timestamp_bits` below 42 means the
+/// remaining high bits are reserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConfigDescriptor {
+    pub timestamp_bits: u8,
+    pub node_bits: u8,
+    pub sequence_bits: u8,
+    pub epoch: u64,
+}
+
+impl From<SnowIDConfig> for ConfigDescriptor {
+    fn from(config: SnowIDConfig) -> Self {
+        Self {
+            timestamp_bits: config.timestamp_bits(),
+            node_bits: config.node_bits(),
+            sequence_bits: config.sequence_bits(),
+            epoch: config.epoch(),
+        }
+    }
+}
+
+impl TryFrom<ConfigDescriptor> for SnowIDConfig {
+    type Error = SnowIDError;
+
+    fn try_from(descriptor: ConfigDescriptor) -> Result<Self, Self::Error> {
+        let reserved_bits = (SnowID::TIMESTAMP_BITS as u8).wrapping_sub(descriptor.timestamp_bits);
+        if reserved_bits > MAX_RESERVED_BITS {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "timestamp must be between 36 and 42 bits",
+            });
+        }
+        if !(6..=16).contains(&descriptor.node_bits) {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "node bits must be between 6 and 16",
+            });
+        }
+        if descriptor.node_bits.checked_add(descriptor.sequence_bits)
+            != Some(SnowID::TOTAL_NODE_AND_SEQUENCE_BITS)
+        {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "node and sequence bits must add up to 22",
+            });
+        }
+
+        Ok(SnowIDConfig::new(descriptor.node_bits, descriptor.epoch)
+            .with_reserved_bits(reserved_bits))
+    }
+}
+
+/// Serializable identity of a generator: its node ID and bit layout
+///
+/// Published by a producer, e.g. to a service registry, so consumers can
+/// rebuild a generator or extractor that decodes its IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeneratorDescriptor {
+    pub node_id: u16,
+    pub config_descriptor: ConfigDescriptor,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::SnowIDExtractor;
 
     mod node_bits_validation {
         use super::*;
@@ -222,12 +850,18 @@ fn test_custom_config() {
     #[test]
     fn test_default_config() {
         let config = SnowIDConfig::default();
+        assert_eq!(config.timestamp_bits(), 42);
         assert_eq!(config.node_bits(), DEFAULT_NODE_BITS);
         assert_eq!(
             config.sequence_bits(),
             SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - DEFAULT_NODE_BITS
         );
         assert_eq!(config.epoch(), DEFAULT_CUSTOM_EPOCH);
+        assert_eq!(
+            config.epoch_systemtime(),
+            UNIX_EPOCH + Duration::from_secs(1_704_067_200)
+        );
+        assert_eq!(config.sequence_policy(), SequencePolicy::RollToNextMs);
     }
 
     #[test]
@@ -236,6 +870,506 @@ fn test_invalid_node_bits() {
         SnowIDConfig::builder().node_bits(21).build();
     }
 
+    mod inference {
+        use super::*;
+
+        fn compose(config: &SnowIDConfig, timestamp: u64, node: u16, sequence: u16) -> u64 {
+            (timestamp << config.timestamp_shift())
+                | ((node as u64) << config.node_shift())
+                | sequence as u64
+        }
+
+        #[test]
+        fn test_infer_known_layout() {
+            for node_bits in [8, 10, 12, 16] {
+                let config = SnowIDConfig::builder().node_bits(node_bits).build();
+                let node = config.max_node_id() / 3;
+                let ids: Vec<u64> = (0..=config.max_sequence_id())
+                    .map(|seq| compose(&config, 1_000_000 + seq as u64 / 7, node, seq))
+                    .collect();
+
+                let inferred = SnowIDConfig::infer_from_samples(&ids).unwrap();
+                assert_eq!(inferred.node_bits(), node_bits);
+                assert_eq!(inferred.epoch(), DEFAULT_CUSTOM_EPOCH);
+            }
+        }
+
+        #[test]
+        fn test_infer_ambiguous_samples() {
+            assert!(SnowIDConfig::infer_from_samples(&[]).is_none());
+
+            // Node 0 with small sequences fits every layout
+            let config = SnowIDConfig::default();
+            let ids: Vec<u64> = (0..10).map(|seq| compose(&config, 5_000, 0, seq)).collect();
+            assert!(SnowIDConfig::infer_from_samples(&ids).is_none());
+        }
+
+        #[test]
+        fn test_infer_generated_samples() {
+            let config = SnowIDConfig::builder().node_bits(12).build();
+            let generator = SnowID::with_config(0b1001, config).unwrap();
+
+            // Real traffic rarely fills the sequence, so only accept a matching layout
+            let ids: Vec<u64> = (0..10_000).map(|_| generator.generate()).collect();
+            if let Some(inferred) = SnowIDConfig::infer_from_samples(&ids) {
+                assert_eq!(inferred.node_bits(), 12);
+            }
+        }
+
+        #[test]
+        fn test_plausible_epoch_for() {
+            let epoch = 1_600_000_000_000;
+            let config = SnowIDConfig::builder().epoch(epoch).build();
+            let generator = SnowID::with_config(1, config).unwrap();
+
+            let ids: Vec<u64> = (0..100).map(|_| generator.generate()).collect();
+            let estimate = config.plausible_epoch_for(&ids).unwrap();
+            assert!(estimate.abs_diff(epoch) < 1_000);
+
+            assert!(config.plausible_epoch_for(&[]).is_none());
+
+            // A newest ID further in the future than the Unix epoch allows is implausible
+            let future = config.timestamp_mask() << config.timestamp_shift();
+            assert!(config.plausible_epoch_for(&[future]).is_none());
+        }
+    }
+
+    #[test]
+    fn test_bit_diagram() {
+        assert_eq!(
+            SnowIDConfig::default().bit_diagram(),
+            "[TTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTTT][NNNNNNNNNN][SSSSSSSSSSSS]"
+        );
+
+        for node_bits in 6..=16 {
+            let diagram = SnowIDConfig::builder()
+                .node_bits(node_bits)
+                .build()
+                .bit_diagram();
+            assert_eq!(
+                diagram.chars().filter(char::is_ascii_alphabetic).count(),
+                64
+            );
+            assert_eq!(diagram.matches('N').count(), node_bits as usize);
+        }
+    }
+
+    #[test]
+    fn test_external_node_round_trip() {
+        for node_bits in [6, 10, 16] {
+            let config = SnowIDConfig::builder().node_bits(node_bits).build();
+            let generator = SnowID::with_config(config.max_node_id() / 2, config).unwrap();
+
+            for _ in 0..100 {
+                let id = generator.generate();
+                let (timestamp, node, sequence) = generator.extract.decompose(id);
+
+                let (value, split_node) = config.split_external_node(id);
+                assert_eq!(split_node, node);
+                assert_eq!(
+                    value,
+                    (timestamp << config.sequence_bits()) | sequence as u64
+                );
+                assert_eq!(config.compose_with_external_node(value, node), Ok(id));
+            }
+        }
+
+        let config = SnowIDConfig::builder().node_bits(6).build();
+        assert_eq!(
+            config.compose_with_external_node(0, 64),
+            Err(SnowIDError::InvalidNodeId {
+                node_id: 64,
+                max: 63
+            })
+        );
+    }
+
+    #[test]
+    fn test_can_contain() {
+        let config = SnowIDConfig::default();
+        assert!(config.can_contain(&config));
+
+        let wide_node = SnowIDConfig::builder().node_bits(12).build();
+        assert!(!config.can_contain(&wide_node));
+        assert!(!wide_node.can_contain(&config));
+
+        // A reserved bit leaves room for a later epoch, but not an earlier one
+        let reserved = SnowIDConfig::builder().reserved_high_bits(1).build();
+        let later = SnowIDConfig::builder()
+            .reserved_high_bits(1)
+            .epoch(config.epoch() + 86_400_000)
+            .build();
+        let earlier = SnowIDConfig::builder()
+            .reserved_high_bits(1)
+            .epoch(config.epoch() - 1)
+            .build();
+        assert!(config.can_contain(&reserved));
+        assert!(config.can_contain(&later));
+        assert!(!config.can_contain(&earlier));
+        assert!(!reserved.can_contain(&config));
+
+        // Same widths only fit with the same epoch
+        let shifted = SnowIDConfig::builder().epoch(config.epoch() + 1).build();
+        assert!(!config.can_contain(&shifted));
+    }
+
+    #[test]
+    fn test_is_truncated() {
+        let config = SnowIDConfig::default();
+        let generator = SnowID::with_config(5, config).unwrap();
+        let id = generator.generate();
+
+        assert!(!config.is_truncated(id, 64));
+        assert!(config.is_truncated(id & 0xFFFF_FFFF, 32));
+        assert!(config.is_truncated(id & 0xFFFF_FFFF_FFFF, 48));
+        assert!(config.is_truncated(id as u16 as u64, 16));
+
+        // About 100 days after the epoch IDs need 56 bits
+        let timestamp = 100 * 86_400_000;
+        let early = generator.create_snowid_with_node(timestamp, 5, 0);
+        assert_eq!(64 - early.leading_zeros(), 56);
+        assert!(!config.is_truncated_at(early, 64, timestamp));
+        assert!(!config.is_truncated_at(early, 56, timestamp));
+        assert!(config.is_truncated_at(early & 0xFFFF_FFFF, 32, timestamp));
+
+        // Right after the epoch even a 32-bit value is a plausible ID
+        let fresh = generator.create_snowid_with_node(10, 5, 0);
+        assert!(!config.is_truncated_at(fresh, 32, 10));
+    }
+
+    #[test]
+    #[should_panic(expected = "Sequence reset base must be below the maximum sequence number")]
+    fn test_sequence_reset_base_too_large() {
+        SnowIDConfig::builder()
+            .node_bits(16)
+            .sequence_reset_base(63)
+            .build();
+    }
+
+    #[test]
+    fn test_ids_per_second_remaining() {
+        let config = SnowIDConfig::default();
+
+        // The default epoch is second-aligned
+        assert_eq!(config.ids_per_second_remaining(5_000, 0), 4095 + 999 * 4096);
+        assert_eq!(
+            config.ids_per_second_remaining(5_500, 95),
+            4000 + 499 * 4096
+        );
+        assert_eq!(config.ids_per_second_remaining(5_999, 4095), 0);
+        assert_eq!(config.ids_per_second_remaining(5_999, 0), 4095);
+
+        let offset = SnowIDConfig::builder()
+            .epoch(config.epoch() + 250)
+            .node_bits(16)
+            .sequence_reset_base(4)
+            .build();
+        assert_eq!(offset.ids_per_second_remaining(0, 4), 59 + 749 * 60);
+    }
+
+    #[test]
+    fn test_dedup_key() {
+        let config = SnowIDConfig::default();
+        let old = SnowID::with_config(3, config).unwrap();
+        let new = SnowID::with_config(700, config).unwrap();
+        let time = config.epoch() + 1_000_000;
+
+        let first = old.generate_with_time(time).unwrap();
+        let retagged = new.generate_with_time(time).unwrap();
+        assert_ne!(first, retagged);
+        assert_eq!(config.dedup_key(first), config.dedup_key(retagged));
+        assert_eq!(
+            old.extract.decompose(config.dedup_key(first)),
+            (1_000_000, 0, 0)
+        );
+
+        let next = old.generate_with_time(time).unwrap();
+        assert_ne!(config.dedup_key(next), config.dedup_key(first));
+    }
+
+    #[test]
+    fn test_time_descending_key() {
+        let config = SnowIDConfig::default();
+        let generator = SnowID::with_config(5, config).unwrap();
+        let time = config.epoch() + 1_000_000;
+
+        let first = generator.generate_with_time(time).unwrap();
+        let second = generator.generate_with_time(time).unwrap();
+        let later = generator.generate_with_time(time + 1).unwrap();
+
+        let key = |id| config.to_time_descending_key(id);
+        // Newer milliseconds first, ascending sequence within a millisecond
+        assert!(key(later) < key(first));
+        assert!(key(first) < key(second));
+
+        assert_eq!(
+            generator.extract.decompose(key(first)),
+            (config.timestamp_mask() - 1_000_000, 5, 0)
+        );
+        for id in [first, second, later, 0, u64::MAX] {
+            assert_eq!(config.from_time_descending_key(key(id)), id);
+        }
+    }
+
+    #[test]
+    fn test_derive_from_hash() {
+        let config = SnowIDConfig::default();
+        let extract = SnowIDExtractor::new(config);
+
+        let id = config.derive_from_hash(1_000_000, b"order-42 created");
+        assert_eq!(id, config.derive_from_hash(1_000_000, b"order-42 created"));
+        assert_eq!(extract.timestamp(id), 1_000_000);
+
+        assert_ne!(id, config.derive_from_hash(1_000_000, b"order-43 created"));
+        let later = config.derive_from_hash(1_000_001, b"order-42 created");
+        assert!(later > id);
+        assert_eq!(later & 0x3F_FFFF, id & 0x3F_FFFF);
+    }
+
+    #[test]
+    fn test_test_vectors() {
+        for config in [
+            SnowIDConfig::default(),
+            SnowIDConfig::builder().node_bits(6).build(),
+            SnowIDConfig::builder()
+                .node_bits(16)
+                .reserved_high_bits(6)
+                .build(),
+        ] {
+            let extract = SnowIDExtractor::new(config);
+            let vectors = config.test_vectors();
+
+            assert_eq!(vectors.len(), 64);
+            assert_eq!(vectors, config.test_vectors());
+            for (timestamp, node, sequence, id, encoded) in vectors {
+                assert_eq!(extract.decompose(id), (timestamp, node, sequence));
+                assert_eq!(crate::decode(&encoded).unwrap(), id);
+            }
+        }
+
+        let vectors = SnowIDConfig::default().test_vectors();
+        assert_eq!(vectors[0], (0, 0, 0, 0, "0000000000000".to_string()));
+        assert_eq!(vectors[63].3, u64::MAX);
+    }
+
+    #[test]
+    fn test_max_nodes() {
+        assert_eq!(SnowIDConfig::default().max_nodes(), 1024);
+
+        for bits in 6..=16 {
+            let config = SnowIDConfig::builder().node_bits(bits).build();
+            assert_eq!(config.max_nodes(), config.max_node_id() as u32 + 1);
+        }
+
+        let config = SnowIDConfig::builder().node_bits(16).build();
+        assert_eq!(config.max_nodes(), 65_536);
+    }
+
+    #[test]
+    fn test_total_id_space() {
+        assert_eq!(SnowIDConfig::default().total_id_space(), 1u128 << 64);
+
+        // Node and sequence bits trade off against each other
+        for bits in 6..=16 {
+            let config = SnowIDConfig::builder().node_bits(bits).build();
+            assert_eq!(config.total_id_space(), u64::MAX as u128 + 1);
+        }
+    }
+
+    #[test]
+    fn test_birthday_collision_probability() {
+        let config = SnowIDConfig::default();
+        assert_eq!(config.birthday_collision_probability(0), 0.0);
+        assert_eq!(config.birthday_collision_probability(1), 0.0);
+        assert!((config.birthday_collision_probability(2) - 1.0 / 1024.0).abs() < 1e-12);
+        assert!((config.birthday_collision_probability(38) - 0.500956).abs() < 1e-5);
+        assert_eq!(config.birthday_collision_probability(1025), 1.0);
+
+        let config = SnowIDConfig::builder().node_bits(16).build();
+        assert!((config.birthday_collision_probability(302) - 0.500722).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_min_node_bits_for() {
+        assert_eq!(SnowIDConfig::min_node_bits_for(0), Some(6));
+        assert_eq!(SnowIDConfig::min_node_bits_for(1), Some(6));
+        assert_eq!(SnowIDConfig::min_node_bits_for(64), Some(6));
+        assert_eq!(SnowIDConfig::min_node_bits_for(65), Some(7));
+        assert_eq!(SnowIDConfig::min_node_bits_for(1024), Some(10));
+        assert_eq!(SnowIDConfig::min_node_bits_for(1025), Some(11));
+        assert_eq!(SnowIDConfig::min_node_bits_for(65_536), Some(16));
+        assert_eq!(SnowIDConfig::min_node_bits_for(65_537), None);
+        assert_eq!(SnowIDConfig::min_node_bits_for(u32::MAX), None);
+
+        let config = SnowIDConfig::builder().node_count(1025).build();
+        assert_eq!(config.node_bits(), 11);
+        assert!(config.max_nodes() >= 1025);
+    }
+
+    #[test]
+    #[should_panic(expected = "Node count must not exceed 65536")]
+    fn test_node_count_too_large() {
+        SnowIDConfig::builder().node_count(65_537);
+    }
+
+    #[test]
+    fn test_reserved_high_bits() {
+        let config = SnowIDConfig::builder().reserved_high_bits(2).build();
+        assert_eq!(config.reserved_high_bits(), 2);
+        assert_eq!(config.timestamp_bits(), 40);
+        assert_eq!(config.total_id_space(), 1 << 62);
+        assert_eq!(
+            config.bit_diagram(),
+            format!(
+                "[RR][{}][{}][{}]",
+                "T".repeat(40),
+                "N".repeat(10),
+                "S".repeat(12)
+            )
+        );
+
+        let descriptor = ConfigDescriptor::from(config);
+        assert_eq!(descriptor.timestamp_bits, 40);
+        let restored = SnowIDConfig::try_from(descriptor).unwrap();
+        assert_eq!(restored.reserved_high_bits(), 2);
+
+        assert_eq!(SnowIDConfig::default().reserved_high_bits(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Reserved bits must be between 0 and 6")]
+    fn test_too_many_reserved_bits() {
+        SnowIDConfig::builder().reserved_high_bits(7);
+    }
+
+    mod descriptor {
+        use super::*;
+
+        #[test]
+        fn test_descriptor_round_trip() {
+            let config = SnowIDConfig::builder()
+                .node_bits(12)
+                .epoch(1640995200000)
+                .build();
+
+            let descriptor = ConfigDescriptor::from(config);
+            assert_eq!(
+                descriptor,
+                ConfigDescriptor {
+                    timestamp_bits: 42,
+                    node_bits: 12,
+                    sequence_bits: 10,
+                    epoch: 1640995200000,
+                }
+            );
+
+            let restored = SnowIDConfig::try_from(descriptor).unwrap();
+            assert_eq!(restored.node_bits(), 12);
+            assert_eq!(restored.sequence_bits(), 10);
+            assert_eq!(restored.epoch(), 1640995200000);
+        }
+
+        #[test]
+        fn test_invalid_descriptors() {
+            let valid = ConfigDescriptor::from(SnowIDConfig::default());
+
+            for invalid in [
+                ConfigDescriptor {
+                    timestamp_bits: 35,
+                    ..valid
+                },
+                ConfigDescriptor {
+                    timestamp_bits: 43,
+                    ..valid
+                },
+                ConfigDescriptor {
+                    node_bits: 5,
+                    sequence_bits: 17,
+                    ..valid
+                },
+                ConfigDescriptor {
+                    sequence_bits: 11,
+                    ..valid
+                },
+            ] {
+                assert!(matches!(
+                    SnowIDConfig::try_from(invalid),
+                    Err(SnowIDError::InvalidLayout { .. })
+                ));
+            }
+        }
+
+        #[test]
+        fn test_overflowing_descriptor_bits() {
+            let malformed = ConfigDescriptor {
+                node_bits: 16,
+                sequence_bits: 250,
+                ..ConfigDescriptor::from(SnowIDConfig::default())
+            };
+
+            assert!(matches!(
+                SnowIDConfig::try_from(malformed),
+                Err(SnowIDError::InvalidLayout { .. })
+            ));
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn test_descriptor_json_round_trip() {
+            let descriptor = ConfigDescriptor::from(SnowIDConfig::default());
+            let json = serde_json::to_string(&descriptor).unwrap();
+            assert_eq!(
+                json,
+                r#"{"timestamp_bits":42,"node_bits":10,"sequence_bits":12,"epoch":1704067200000}"#
+            );
+
+            let decoded: ConfigDescriptor = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, descriptor);
+            assert!(SnowIDConfig::try_from(decoded).is_ok());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn test_generator_descriptor_json_round_trip() {
+            let config = SnowIDConfig::builder().node_bits(12).build();
+            let producer = SnowID::with_config(1234, config).unwrap();
+
+            let json = serde_json::to_string(&producer.descriptor()).unwrap();
+            assert_eq!(
+                json,
+                r#"{"node_id":1234,"config_descriptor":{"timestamp_bits":42,"node_bits":12,"sequence_bits":10,"epoch":1704067200000}}"#
+            );
+
+            let descriptor: GeneratorDescriptor = serde_json::from_str(&json).unwrap();
+            let consumer = SnowID::from_descriptor(&descriptor).unwrap();
+            assert_eq!(consumer.node_id(), 1234);
+
+            let id = producer.generate();
+            assert_eq!(
+                consumer.extract.decompose(id),
+                producer.extract.decompose(id)
+            );
+        }
+
+        #[test]
+        fn test_invalid_generator_descriptor() {
+            let mut descriptor = SnowID::new(5).unwrap().descriptor();
+
+            descriptor.node_id = 1024;
+            assert!(matches!(
+                SnowID::from_descriptor(&descriptor),
+                Err(SnowIDError::InvalidNodeId { .. })
+            ));
+
+            descriptor.node_id = 5;
+            descriptor.config_descriptor.sequence_bits = 11;
+            assert!(matches!(
+                SnowID::from_descriptor(&descriptor),
+                Err(SnowIDError::InvalidLayout { .. })
+            ));
+        }
+    }
+
     #[test]
     fn test_bit_config() {
         let config = SnowIDConfig::default();
@@ -0,0 +1,993 @@
+use crate::error::SnowIDError;
+
+/// Length of a base32-encoded SnowID (64 bits in 5-bit symbols, zero-padded)
+pub const ENCODED_LEN: usize = 13;
+
+/// Length of a ULID-compatible SnowID string (128 bits in 5-bit symbols)
+pub const ULID_LEN: usize = 26;
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const RFC4648: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32_HEX: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+
+const INVALID: u8 = u8::MAX;
+
+/// Crockford check symbols for values 32-36, following the 32 regular digits
+const CHECK_SYMBOLS: &[u8; 5] = b"*~$=U";
+
+/// Base32 alphabet used to encode and decode SnowIDs
+///
+/// Crockford and Base32Hex preserve numeric order when encoded strings are
+/// compared lexicographically. RFC 4648 does not, as its digits sort after its letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alphabet {
+    /// Crockford base32 (`0-9`, `A-Z` without `I`, `L`, `O`, `U`)
+    #[default]
+    Crockford,
+    /// RFC 4648 base32 (`A-Z`, `2-7`)
+    Rfc4648,
+    /// RFC 4648 "extended hex" base32 (`0-9`, `A-V`)
+    Base32Hex,
+    /// User-supplied table of 32 unique ASCII characters
+    Custom(CustomAlphabet),
+}
+
+/// Validated table of 32 unique ASCII characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomAlphabet {
+    symbols: [u8; 32],
+}
+
+impl Alphabet {
+    /// Create a custom alphabet from a 32-character table
+    ///
+    /// # Arguments
+    /// * `symbols` - 32 unique ASCII characters, ordered from digit value 0 to 31
+    ///
+    /// # Returns
+    /// * `Result<Alphabet, SnowIDError>` - Custom alphabet or error if the table is invalid
+    pub fn custom(symbols: &str) -> Result<Self, SnowIDError> {
+        if !symbols.is_ascii() {
+            return Err(SnowIDError::InvalidAlphabet {
+                reason: "symbols must be ASCII characters",
+            });
+        }
+
+        let bytes = symbols.as_bytes();
+        if bytes.len() != 32 {
+            return Err(SnowIDError::InvalidAlphabet {
+                reason: "alphabet must contain exactly 32 characters",
+            });
+        }
+
+        let mut seen = [false; 128];
+        for &symbol in bytes {
+            if seen[symbol as usize] {
+                return Err(SnowIDError::InvalidAlphabet {
+                    reason: "alphabet characters must be unique",
+                });
+            }
+            seen[symbol as usize] = true;
+        }
+
+        let mut table = [0u8; 32];
+        table.copy_from_slice(bytes);
+        Ok(Alphabet::Custom(CustomAlphabet { symbols: table }))
+    }
+
+    /// Get the 32 symbols of this alphabet, ordered by digit value
+    #[inline]
+    pub fn symbols(&self) -> &[u8; 32] {
+        match self {
+            Alphabet::Crockford => CROCKFORD,
+            Alphabet::Rfc4648 => RFC4648,
+            Alphabet::Base32Hex => BASE32_HEX,
+            Alphabet::Custom(custom) => &custom.symbols,
+        }
+    }
+
+    /// Build a reverse lookup table from ASCII byte to digit value
+    fn decode_table(&self) -> [u8; 128] {
+        let mut table = [INVALID; 128];
+
+        for (value, &symbol) in self.symbols().iter().enumerate() {
+            table[symbol as usize] = value as u8;
+        }
+
+        // Built-in alphabets are case-insensitive; custom ones are matched exactly
+        if !matches!(self, Alphabet::Custom(_)) {
+            for (value, &symbol) in self.symbols().iter().enumerate() {
+                table[symbol.to_ascii_lowercase() as usize] = value as u8;
+            }
+        }
+
+        // Crockford treats commonly confused characters as their look-alike digits
+        if let Alphabet::Crockford = self {
+            for (alias, value) in [(b'O', 0), (b'I', 1), (b'L', 1)] {
+                table[alias as usize] = value;
+                table[alias.to_ascii_lowercase() as usize] = value;
+            }
+        }
+
+        table
+    }
+}
+
+/// Encode a SnowID as a 13-character Crockford base32 string
+///
+/// # Arguments
+/// * `id` - SnowID value to encode
+///
+/// # Returns
+/// * `String` - Fixed-width, zero-padded encoding that sorts like the numeric value
+pub fn encode(id: u64) -> String {
+    encode_with(id, Alphabet::Crockford)
+}
+
+/// Encode a SnowID as a 13-character base32 string using the given alphabet
+///
+/// # Arguments
+/// * `id` - SnowID value to encode
+/// * `alphabet` - Alphabet to map 5-bit digits to characters
+///
+/// # Returns
+/// * `String` - Fixed-width, zero-padded encoding
+pub fn encode_with(id: u64, alphabet: Alphabet) -> String {
+    let mut buf = [0u8; ENCODED_LEN];
+    encode_to_slice(id, alphabet, &mut buf);
+    buf.iter().map(|&b| b as char).collect()
+}
+
+/// Decode a 13-character Crockford base32 string into a SnowID
+///
+/// Decoding is case-insensitive and accepts `O` for `0` and `I`/`L` for `1`.
+///
+/// # Arguments
+/// * `encoded` - Encoded SnowID string
+///
+/// # Returns
+/// * `Result<u64, SnowIDError>` - Decoded SnowID value or error if the string is malformed
+pub fn decode(encoded: &str) -> Result<u64, SnowIDError> {
+    decode_with(encoded, Alphabet::Crockford)
+}
+
+/// Decode a 13-character base32 string into a SnowID using the given alphabet
+///
+/// # Arguments
+/// * `encoded` - Encoded SnowID string
+/// * `alphabet` - Alphabet the string was encoded with
+///
+/// # Returns
+/// * `Result<u64, SnowIDError>` - Decoded SnowID value or error if the string is malformed
+pub fn decode_with(encoded: &str, alphabet: Alphabet) -> Result<u64, SnowIDError> {
+    let bytes = encoded.as_bytes();
+    if bytes.len() != ENCODED_LEN {
+        return Err(SnowIDError::InvalidLength {
+            expected: ENCODED_LEN,
+            actual: bytes.len(),
+        });
+    }
+
+    let table = alphabet.decode_table();
+    let mut id: u64 = 0;
+
+    for (position, &byte) in bytes.iter().enumerate() {
+        let value = table.get(byte as usize).copied().unwrap_or(INVALID);
+        if value == INVALID {
+            return Err(SnowIDError::InvalidCharacter {
+                character: encoded[position..].chars().next().unwrap_or_default(),
+                position,
+            });
+        }
+
+        // The leading symbol only carries the top 4 bits of the 64-bit value
+        if position == 0 && value > 0x0F {
+            return Err(SnowIDError::EncodingOverflow);
+        }
+
+        id = (id << 5) | value as u64;
+    }
+
+    Ok(id)
+}
+
+/// Encode a SnowID left-padded to a fixed width
+///
+/// Pads with the alphabet's zero symbol, so keys of one width keep the numeric
+/// sort order of sortable alphabets, e.g. uniform 16-character keys across ID
+/// types. Widths below 13 only fit IDs whose leading digits are zero.
+///
+/// # Arguments
+/// * `id` - SnowID value to encode
+/// * `width` - Total length of the result
+/// * `alphabet` - Alphabet to encode with
+///
+/// # Returns
+/// * `Result<String, SnowIDError>` - Padded encoding or error if the ID needs more than `width` characters
+pub fn encode_padded(id: u64, width: usize, alphabet: Alphabet) -> Result<String, SnowIDError> {
+    let mut buf = [0u8; ENCODED_LEN];
+    encode_to_slice(id, alphabet, &mut buf);
+
+    let zero = alphabet.symbols()[0];
+    let leading_zeros = buf.iter().take_while(|&&symbol| symbol == zero).count();
+    let digits = &buf[leading_zeros.min(ENCODED_LEN - 1)..];
+
+    if digits.len() > width {
+        return Err(SnowIDError::InvalidLength {
+            expected: width,
+            actual: digits.len(),
+        });
+    }
+
+    let mut padded = vec![zero; width - digits.len()];
+    padded.extend_from_slice(digits);
+    Ok(String::from_utf8(padded).expect("alphabet symbols are ASCII"))
+}
+
+/// Decode a string produced by `encode_padded`, whatever its width
+///
+/// # Arguments
+/// * `encoded` - Padded encoded SnowID
+/// * `alphabet` - Alphabet the string was encoded with
+///
+/// # Returns
+/// * `Result<u64, SnowIDError>` - Decoded SnowID value or error if the string is malformed
+pub fn decode_padded(encoded: &str, alphabet: Alphabet) -> Result<u64, SnowIDError> {
+    let zero = alphabet.symbols()[0] as char;
+    let digits = encoded.trim_start_matches(zero);
+    if digits.len() > ENCODED_LEN {
+        return Err(SnowIDError::EncodingOverflow);
+    }
+
+    let padding = ENCODED_LEN - digits.len();
+    let padded = format!("{}{}", zero.to_string().repeat(padding), digits);
+
+    decode_with(&padded, alphabet).map_err(|error| match error {
+        SnowIDError::InvalidCharacter {
+            character,
+            position,
+        } => SnowIDError::InvalidCharacter {
+            character,
+            position: position - padding + (encoded.len() - digits.len()),
+        },
+        error => error,
+    })
+}
+
+/// Encode a SnowID as Crockford base32 followed by a check symbol
+///
+/// The extra character is `id mod 37` from the Crockford check alphabet
+/// (`0-9A-Z` minus `ILOU`, then `*~$=U`), which detects any single-character
+/// transcription error. Plain `decode` does not accept these 14-character strings.
+///
+/// # Arguments
+/// * `id` - SnowID value to encode
+///
+/// # Returns
+/// * `String` - 14-character encoding with trailing check symbol
+pub fn encode_checked(id: u64) -> String {
+    let mut encoded = encode(id);
+    encoded.push(check_symbol(id) as char);
+    encoded
+}
+
+/// Decode a string produced by `encode_checked`, validating its check symbol
+///
+/// # Arguments
+/// * `encoded` - 14-character encoded SnowID with trailing check symbol
+///
+/// # Returns
+/// * `Result<u64, SnowIDError>` - Decoded SnowID value or error if malformed or the checksum fails
+pub fn decode_checked(encoded: &str) -> Result<u64, SnowIDError> {
+    if encoded.len() != ENCODED_LEN + 1 || !encoded.is_char_boundary(ENCODED_LEN) {
+        return Err(SnowIDError::InvalidLength {
+            expected: ENCODED_LEN + 1,
+            actual: encoded.len(),
+        });
+    }
+
+    let (digits, check) = encoded.split_at(ENCODED_LEN);
+    let id = decode(digits)?;
+
+    if !check.eq_ignore_ascii_case(&(check_symbol(id) as char).to_string()) {
+        return Err(SnowIDError::BadChecksum);
+    }
+
+    Ok(id)
+}
+
+/// Get the Crockford check symbol for a value
+fn check_symbol(id: u64) -> u8 {
+    match (id % 37) as usize {
+        value @ 0..32 => CROCKFORD[value],
+        value => CHECK_SYMBOLS[value - 32],
+    }
+}
+
+/// Encode a SnowID as a key for lexicographically sorted stores
+///
+/// The contract is fixed: always 13 characters, uppercase Crockford base32,
+/// left-padded with `0`, so that `a < b` implies `to_sort_key(a) < to_sort_key(b)`
+/// under byte-wise comparison (e.g. DynamoDB sort keys or S3 object prefixes).
+///
+/// # Arguments
+/// * `id` - SnowID value to encode
+///
+/// # Returns
+/// * `String` - Sortable key
+pub fn to_sort_key(id: u64) -> String {
+    encode(id)
+}
+
+/// Decode a key produced by `to_sort_key`
+///
+/// Unlike `decode`, only the canonical form is accepted: lowercase letters and
+/// Crockford aliases are rejected, since they would not sort correctly.
+///
+/// # Arguments
+/// * `key` - Sortable key
+///
+/// # Returns
+/// * `Result<u64, SnowIDError>` - Decoded SnowID value or error if the key is not canonical
+pub fn from_sort_key(key: &str) -> Result<u64, SnowIDError> {
+    let id = decode(key)?;
+
+    if let Some(position) = key
+        .bytes()
+        .zip(encode(id).bytes())
+        .position(|(a, b)| a != b)
+    {
+        return Err(SnowIDError::InvalidCharacter {
+            character: key.as_bytes()[position] as char,
+            position,
+        });
+    }
+
+    Ok(id)
+}
+
+/// Convert a SnowID into an `i64` that sorts in the same order
+///
+/// A plain `as i64` cast sorts IDs with the high bit set before all others.
+/// Flipping the sign bit maps `0..=u64::MAX` onto `i64::MIN..=i64::MAX`
+/// monotonically, which keeps signed database columns correctly ordered.
+///
+/// # Arguments
+/// * `id` - SnowID value to convert
+///
+/// # Returns
+/// * `i64` - Order-preserving signed representation
+#[inline]
+pub fn to_sortable_i64(id: u64) -> i64 {
+    (id ^ (1 << 63)) as i64
+}
+
+/// Convert a value produced by `to_sortable_i64` back into a SnowID
+///
+/// # Arguments
+/// * `value` - Order-preserving signed representation
+///
+/// # Returns
+/// * `u64` - SnowID value
+#[inline]
+pub fn from_sortable_i64(value: i64) -> u64 {
+    value as u64 ^ (1 << 63)
+}
+
+/// Convert a SnowID into its 8-byte big-endian representation
+///
+/// Big-endian bytes compare in the same order as the numeric value.
+///
+/// # Arguments
+/// * `id` - SnowID value to convert
+///
+/// # Returns
+/// * `[u8; 8]` - Big-endian bytes of the SnowID
+#[inline]
+pub fn to_bytes(id: u64) -> [u8; 8] {
+    id.to_be_bytes()
+}
+
+/// Reconstruct a SnowID from a big-endian byte slice
+///
+/// # Arguments
+/// * `bytes` - Exactly 8 big-endian bytes, e.g. read from a buffer
+///
+/// # Returns
+/// * `Result<u64, SnowIDError>` - SnowID value or error if the slice is not 8 bytes long
+pub fn from_bytes(bytes: &[u8]) -> Result<u64, SnowIDError> {
+    let bytes: [u8; 8] = bytes.try_into().map_err(|_| SnowIDError::InvalidLength {
+        expected: 8,
+        actual: bytes.len(),
+    })?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+/// Compute the Kafka partition of a record keyed by a SnowID
+///
+/// Matches Kafka's default partitioner for a key serialized with
+/// `LongSerializer`: the 8 big-endian bytes of `to_bytes` are hashed with
+/// Kafka's murmur2 (seed `0x9747b28c`), the sign bit is cleared and the result
+/// is taken modulo `partitions`.
+///
+/// # Arguments
+/// * `id` - SnowID used as the record key
+/// * `partitions` - Number of partitions of the topic
+///
+/// # Returns
+/// * `i32` - Partition in `0..partitions`
+///
+/// # Panics
+/// Panics if `partitions` is not positive
+pub fn kafka_partition(id: u64, partitions: i32) -> i32 {
+    assert!(partitions > 0, "Partition count must be positive");
+    (murmur2(&to_bytes(id)) & 0x7FFF_FFFF) % partitions
+}
+
+/// Kafka's variant of MurmurHash2, as in `org.apache.kafka.common.utils.Utils`
+pub(crate) fn murmur2(data: &[u8]) -> i32 {
+    const M: u32 = 0x5BD1_E995;
+    const R: u32 = 24;
+
+    let mut h = 0x9747_B28C ^ data.len() as u32;
+
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes(chunk.try_into().expect("chunk has 4 bytes"));
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M) ^ k;
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, &byte) in tail.iter().enumerate() {
+            h ^= (byte as u32) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h as i32
+}
+
+/// Encode a SnowID in base N over an arbitrary symbol table
+///
+/// Each digit maps to a caller-provided symbol, such as a word or an emoji, which
+/// makes for memorable short codes. The result is not padded, so it is only as long
+/// as the value requires and does not sort like the numeric value.
+///
+/// # Arguments
+/// * `id` - SnowID value to encode
+/// * `symbols` - At least 2 non-empty symbols, ordered from digit value 0, none a prefix of another
+///
+/// # Returns
+/// * `Result<String, SnowIDError>` - Encoded SnowID or error if the symbol table is invalid
+pub fn encode_with_symbols(id: u64, symbols: &[&str]) -> Result<String, SnowIDError> {
+    validate_symbols(symbols)?;
+
+    let base = symbols.len() as u64;
+    let mut digits = Vec::new();
+    let mut value = id;
+
+    loop {
+        digits.push(symbols[(value % base) as usize]);
+        value /= base;
+        if value == 0 {
+            break;
+        }
+    }
+
+    Ok(digits.into_iter().rev().collect())
+}
+
+/// Decode a string produced by `encode_with_symbols`
+///
+/// Because no symbol is a prefix of another, the next symbol in the input is
+/// always unique, so no separators are needed.
+///
+/// # Arguments
+/// * `encoded` - Encoded SnowID
+/// * `symbols` - Symbol table the string was encoded with
+///
+/// # Returns
+/// * `Result<u64, SnowIDError>` - Decoded SnowID value or error if the string or table is invalid
+pub fn decode_with_symbols(encoded: &str, symbols: &[&str]) -> Result<u64, SnowIDError> {
+    validate_symbols(symbols)?;
+
+    if encoded.is_empty() {
+        return Err(SnowIDError::InvalidLength {
+            expected: 1,
+            actual: 0,
+        });
+    }
+
+    let base = symbols.len() as u64;
+    let mut id: u64 = 0;
+    let mut position = 0;
+
+    while position < encoded.len() {
+        let rest = &encoded[position..];
+        let Some((value, symbol)) = symbols
+            .iter()
+            .enumerate()
+            .find(|(_, symbol)| rest.starts_with(**symbol))
+        else {
+            return Err(SnowIDError::InvalidCharacter {
+                character: rest.chars().next().unwrap_or_default(),
+                position,
+            });
+        };
+
+        id = id
+            .checked_mul(base)
+            .and_then(|id| id.checked_add(value as u64))
+            .ok_or(SnowIDError::EncodingOverflow)?;
+        position += symbol.len();
+    }
+
+    Ok(id)
+}
+
+/// Check that a symbol table can be decoded unambiguously
+fn validate_symbols(symbols: &[&str]) -> Result<(), SnowIDError> {
+    if symbols.len() < 2 {
+        return Err(SnowIDError::InvalidAlphabet {
+            reason: "symbol table must contain at least 2 symbols",
+        });
+    }
+
+    if symbols.iter().any(|symbol| symbol.is_empty()) {
+        return Err(SnowIDError::InvalidAlphabet {
+            reason: "symbols must not be empty",
+        });
+    }
+
+    for (i, a) in symbols.iter().enumerate() {
+        for b in &symbols[i + 1..] {
+            if a == b {
+                return Err(SnowIDError::InvalidAlphabet {
+                    reason: "symbols must be unique",
+                });
+            }
+            if a.starts_with(b) || b.starts_with(a) {
+                return Err(SnowIDError::InvalidAlphabet {
+                    reason: "no symbol may be a prefix of another",
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the Crockford encoding of `id` into a caller-provided buffer
+pub(crate) fn encode_into(id: u64, buf: &mut [u8; ENCODED_LEN]) -> &str {
+    encode_to_slice(id, Alphabet::Crockford, buf);
+    std::str::from_utf8(buf).expect("Crockford symbols are ASCII")
+}
+
+/// Encode a 128-bit value as a 26-character Crockford base32 string, as in ULIDs
+pub(crate) fn encode_u128(value: u128) -> String {
+    let mut buf = [0u8; ULID_LEN];
+    let mut value = value;
+
+    for slot in buf.iter_mut().rev() {
+        *slot = CROCKFORD[(value & 0x1F) as usize];
+        value >>= 5;
+    }
+
+    buf.iter().map(|&b| b as char).collect()
+}
+
+/// Decode a 26-character Crockford base32 string into a 128-bit value
+pub(crate) fn decode_u128(encoded: &str) -> Result<u128, SnowIDError> {
+    let bytes = encoded.as_bytes();
+    if bytes.len() != ULID_LEN {
+        return Err(SnowIDError::InvalidLength {
+            expected: ULID_LEN,
+            actual: bytes.len(),
+        });
+    }
+
+    let table = Alphabet::Crockford.decode_table();
+    let mut value: u128 = 0;
+
+    for (position, &byte) in bytes.iter().enumerate() {
+        let digit = table.get(byte as usize).copied().unwrap_or(INVALID);
+        if digit == INVALID {
+            return Err(SnowIDError::InvalidCharacter {
+                character: encoded[position..].chars().next().unwrap_or_default(),
+                position,
+            });
+        }
+
+        // The leading symbol only carries the top 3 bits of the 128-bit value
+        if position == 0 && digit > 0x07 {
+            return Err(SnowIDError::EncodingOverflow);
+        }
+
+        value = (value << 5) | digit as u128;
+    }
+
+    Ok(value)
+}
+
+/// Write the encoding of `id` into a fixed-size buffer
+fn encode_to_slice(id: u64, alphabet: Alphabet, buf: &mut [u8; ENCODED_LEN]) {
+    let symbols = alphabet.symbols();
+    let mut value = id;
+
+    for slot in buf.iter_mut().rev() {
+        *slot = symbols[(value & 0x1F) as usize];
+        value >>= 5;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALPHABETS: [Alphabet; 3] = [Alphabet::Crockford, Alphabet::Rfc4648, Alphabet::Base32Hex];
+
+    #[test]
+    fn test_round_trip_all_alphabets() {
+        let samples = [0, 1, 31, 32, 151819733950271234, u64::MAX - 1, u64::MAX];
+
+        for alphabet in ALPHABETS {
+            for &id in &samples {
+                let encoded = encode_with(id, alphabet);
+                assert_eq!(encoded.len(), ENCODED_LEN);
+                assert_eq!(decode_with(&encoded, alphabet).unwrap(), id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_encodings() {
+        assert_eq!(encode(0), "0000000000000");
+        assert_eq!(encode(u64::MAX), "FZZZZZZZZZZZZ");
+        assert_eq!(encode_with(0, Alphabet::Rfc4648), "AAAAAAAAAAAAA");
+        assert_eq!(encode_with(u64::MAX, Alphabet::Base32Hex), "FVVVVVVVVVVVV");
+    }
+
+    #[test]
+    fn test_crockford_is_lenient() {
+        let id = decode("0000000000001").unwrap();
+        assert_eq!(decode("000000000000i").unwrap(), id);
+        assert_eq!(decode("000000000000L").unwrap(), id);
+        assert_eq!(decode("OOOOOOOOOOOO1").unwrap(), id);
+        assert_eq!(decode("fzzzzzzzzzzzz").unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_sort_order_preserved() {
+        let ids = [1u64, 1000, 1 << 40, u64::MAX / 3, u64::MAX];
+        for alphabet in [Alphabet::Crockford, Alphabet::Base32Hex] {
+            for pair in ids.windows(2) {
+                assert!(encode_with(pair[0], alphabet) < encode_with(pair[1], alphabet));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(
+            decode("000"),
+            Err(SnowIDError::InvalidLength {
+                expected: ENCODED_LEN,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            decode("00000000000U0"),
+            Err(SnowIDError::InvalidCharacter {
+                character: 'U',
+                position: 11
+            })
+        );
+        assert_eq!(decode("G000000000000"), Err(SnowIDError::EncodingOverflow));
+        assert!(decode_with("0000000000000", Alphabet::Rfc4648).is_err());
+    }
+
+    #[test]
+    fn test_sort_key_ordering() {
+        let ids = [0u64, 1, 31, 32, 1 << 22, 151819733950271234, u64::MAX];
+
+        for a in ids {
+            let key = to_sort_key(a);
+            assert_eq!(key.len(), ENCODED_LEN);
+            assert_eq!(key, key.to_uppercase());
+            assert_eq!(from_sort_key(&key).unwrap(), a);
+
+            for b in ids {
+                assert_eq!(a.cmp(&b), key.cmp(&to_sort_key(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_key_rejects_non_canonical() {
+        assert!(from_sort_key("000000000000a").is_err());
+        assert_eq!(
+            from_sort_key("O000000000000"),
+            Err(SnowIDError::InvalidCharacter {
+                character: 'O',
+                position: 0
+            })
+        );
+        assert!(from_sort_key("0000").is_err());
+    }
+
+    #[test]
+    fn test_checked_round_trip() {
+        for id in [0, 1, 36, 37, 151819733950271234, u64::MAX] {
+            let encoded = encode_checked(id);
+            assert_eq!(encoded.len(), ENCODED_LEN + 1);
+            assert_eq!(&encoded[..ENCODED_LEN], encode(id));
+            assert_eq!(decode_checked(&encoded).unwrap(), id);
+            assert_eq!(decode_checked(&encoded.to_lowercase()).unwrap(), id);
+        }
+
+        assert!(encode_checked(32).ends_with('*'));
+        assert!(encode_checked(36).ends_with('U'));
+        assert!(decode(&encode_checked(1)).is_err());
+    }
+
+    #[test]
+    fn test_checked_detects_single_char_errors() {
+        let id = 151819733950271234;
+        let encoded = encode_checked(id);
+
+        for position in 0..ENCODED_LEN {
+            for &replacement in CROCKFORD {
+                let mut corrupted = encoded.clone().into_bytes();
+                if corrupted[position] == replacement {
+                    continue;
+                }
+                corrupted[position] = replacement;
+                let corrupted = String::from_utf8(corrupted).unwrap();
+
+                // Out-of-range leading digits are rejected before the checksum
+                if decode(&corrupted[..ENCODED_LEN]).is_err() {
+                    continue;
+                }
+                assert_eq!(decode_checked(&corrupted), Err(SnowIDError::BadChecksum));
+            }
+        }
+
+        assert!(decode_checked(&encoded[..ENCODED_LEN]).is_err());
+    }
+
+    #[test]
+    fn test_sortable_i64() {
+        let boundary = 1u64 << 63;
+        let ids = [
+            0,
+            1,
+            boundary - 2,
+            boundary - 1,
+            boundary,
+            boundary + 1,
+            u64::MAX - 1,
+            u64::MAX,
+        ];
+
+        for &id in &ids {
+            assert_eq!(from_sortable_i64(to_sortable_i64(id)), id);
+        }
+        for pair in ids.windows(2) {
+            assert!(to_sortable_i64(pair[0]) < to_sortable_i64(pair[1]));
+        }
+
+        assert_eq!(to_sortable_i64(0), i64::MIN);
+        assert_eq!(to_sortable_i64(u64::MAX), i64::MAX);
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        for id in [0, 1, 151819733950271234, u64::MAX] {
+            assert_eq!(from_bytes(&to_bytes(id)).unwrap(), id);
+        }
+        assert_eq!(to_bytes(1), [0, 0, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_from_bytes_invalid_length() {
+        assert_eq!(
+            from_bytes(&[0; 7]),
+            Err(SnowIDError::InvalidLength {
+                expected: 8,
+                actual: 7
+            })
+        );
+        assert_eq!(
+            from_bytes(&[0; 9]),
+            Err(SnowIDError::InvalidLength {
+                expected: 8,
+                actual: 9
+            })
+        );
+        assert!(from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_custom_alphabet() {
+        let alphabet = Alphabet::custom("abcdefghijklmnopqrstuvwxyz!@#$%^").unwrap();
+        let id = 151819733950271234;
+        let encoded = encode_with(id, alphabet);
+        assert!(encoded
+            .chars()
+            .all(|c| alphabet.symbols().contains(&(c as u8))));
+        assert_eq!(decode_with(&encoded, alphabet).unwrap(), id);
+
+        // Custom alphabets are case-sensitive
+        assert!(decode_with(&encoded.to_uppercase(), alphabet).is_err());
+    }
+
+    #[test]
+    fn test_invalid_custom_alphabets() {
+        assert!(matches!(
+            Alphabet::custom("0123456789"),
+            Err(SnowIDError::InvalidAlphabet { .. })
+        ));
+        assert!(matches!(
+            Alphabet::custom("00123456789ABCDEFGHJKMNPQRSTVWXY"),
+            Err(SnowIDError::InvalidAlphabet { .. })
+        ));
+        assert!(matches!(
+            Alphabet::custom("0123456789ABCDEFGHJKMNPQRSTVWXYé"),
+            Err(SnowIDError::InvalidAlphabet { .. })
+        ));
+    }
+
+    const WORDS: [&str; 8] = ["ant", "bee", "cat", "dog", "elk", "fox", "gnu", "hen"];
+
+    #[test]
+    fn test_symbols_round_trip() {
+        assert_eq!(encode_with_symbols(0, &WORDS).unwrap(), "ant");
+        assert_eq!(encode_with_symbols(9, &WORDS).unwrap(), "beebee");
+
+        // Variable-width symbols stay unambiguous as long as the table is prefix-free
+        let emoji = ["🍎", "🍌", "🍒", "x", "yz"];
+        for id in [0, 1, 7, 8, 151819733950271234, u64::MAX] {
+            let words = encode_with_symbols(id, &WORDS).unwrap();
+            assert_eq!(decode_with_symbols(&words, &WORDS).unwrap(), id);
+
+            let code = encode_with_symbols(id, &emoji).unwrap();
+            assert_eq!(decode_with_symbols(&code, &emoji).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn test_invalid_symbol_tables() {
+        for symbols in [
+            &["a"][..],
+            &["a", ""][..],
+            &["cat", "dog", "cat"][..],
+            &["cat", "catalog"][..],
+        ] {
+            assert!(matches!(
+                encode_with_symbols(1, symbols),
+                Err(SnowIDError::InvalidAlphabet { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_decode_with_symbols_errors() {
+        assert_eq!(
+            decode_with_symbols("antcow", &WORDS),
+            Err(SnowIDError::InvalidCharacter {
+                character: 'c',
+                position: 3
+            })
+        );
+        assert!(matches!(
+            decode_with_symbols("", &WORDS),
+            Err(SnowIDError::InvalidLength { .. })
+        ));
+
+        let too_long = encode_with_symbols(u64::MAX, &WORDS).unwrap() + "hen";
+        assert_eq!(
+            decode_with_symbols(&too_long, &WORDS),
+            Err(SnowIDError::EncodingOverflow)
+        );
+    }
+
+    #[test]
+    fn test_padded_round_trip() {
+        assert_eq!(
+            encode_padded(0, 16, Alphabet::Crockford).unwrap(),
+            "0000000000000000"
+        );
+        assert_eq!(encode_padded(31, 3, Alphabet::Crockford).unwrap(), "00Z");
+        assert_eq!(encode_padded(32, 2, Alphabet::Rfc4648).unwrap(), "BA");
+
+        for &id in &[0, 1, 1 << 40, 151819733950271234, u64::MAX] {
+            for alphabet in ALPHABETS {
+                let padded = encode_padded(id, 16, alphabet).unwrap();
+                assert_eq!(padded.len(), 16);
+                assert_eq!(decode_padded(&padded, alphabet).unwrap(), id);
+            }
+        }
+
+        let mut keys: Vec<String> = [7, 1 << 20, 1 << 50, 3]
+            .iter()
+            .map(|&id| encode_padded(id, 16, Alphabet::Crockford).unwrap())
+            .collect();
+        keys.sort();
+        let ids: Vec<u64> = keys
+            .iter()
+            .map(|key| decode_padded(key, Alphabet::Crockford).unwrap())
+            .collect();
+        assert_eq!(ids, vec![3, 7, 1 << 20, 1 << 50]);
+    }
+
+    #[test]
+    fn test_padded_errors() {
+        assert_eq!(
+            encode_padded(u64::MAX, 12, Alphabet::Crockford),
+            Err(SnowIDError::InvalidLength {
+                expected: 12,
+                actual: 13
+            })
+        );
+        assert_eq!(
+            encode_padded(32, 1, Alphabet::Crockford),
+            Err(SnowIDError::InvalidLength {
+                expected: 1,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            decode_padded("1000000000000000", Alphabet::Crockford),
+            Err(SnowIDError::EncodingOverflow)
+        );
+        assert_eq!(
+            decode_padded("0000!1", Alphabet::Crockford),
+            Err(SnowIDError::InvalidCharacter {
+                character: '!',
+                position: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_murmur2_matches_kafka() {
+        // Vectors from Kafka's own UtilsTest
+        let cases: [(&[u8], i32); 6] = [
+            (b"21", -973932308),
+            (b"foobar", -790332482),
+            (b"a-little-bit-long-string", -985981536),
+            (b"a-little-bit-longer-string", -1486304829),
+            (
+                b"lkjh234lh9fiuh90y23oiuhsafujhadof229phr9h19h89h8",
+                -58897971,
+            ),
+            (b"abc", 479470107),
+        ];
+
+        for (data, expected) in cases {
+            assert_eq!(murmur2(data), expected);
+        }
+    }
+
+    #[test]
+    fn test_kafka_partition() {
+        assert_eq!(murmur2(&to_bytes(151819733950271234)), -1063030787);
+        assert_eq!(kafka_partition(151819733950271234, 12), 9);
+        assert_eq!(kafka_partition(0, 12), 11);
+        assert_eq!(kafka_partition(1, 12), 0);
+        assert_eq!(kafka_partition(151819733950271234, 1), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Partition count must be positive")]
+    fn test_kafka_partition_requires_partitions() {
+        kafka_partition(1, 0);
+    }
+}
@@ -9,6 +9,59 @@ pub enum SnowIDError {
     /// Error when clock moves backwards (system time issue)
     #[error("Clock moved backwards. Refusing to generate id for {delta} milliseconds")]
     ClockMovedBackwards { delta: i64 },
+    /// Error when a supplied time lies before the configured epoch
+    #[error("Time {time} is before epoch {epoch}")]
+    TimeBeforeEpoch { time: u64, epoch: u64 },
+    /// Error when the sequence is exhausted and the policy forbids waiting
+    #[error("Sequence exhausted for timestamp {timestamp}")]
+    SequenceExhausted { timestamp: u64 },
+    /// Error when no SnowID greater than the requested floor can be generated
+    #[error("Floor {floor} leaves no room for a greater SnowID")]
+    FloorOutOfRange { floor: u64 },
+    /// Error when the generator's node ID does not map to the requested shard
+    #[error("Node ID {node_id} does not belong to shard {shard} of {num_shards}")]
+    ShardMismatch {
+        node_id: u16,
+        shard: u16,
+        num_shards: u16,
+    },
+    /// Error when a SnowID component does not fit in its bits
+    #[error("{component} {value} is out of range. Maximum allowed value is {max}")]
+    ComponentOutOfRange {
+        component: &'static str,
+        value: u64,
+        max: u64,
+    },
+    /// Error when a bit layout description does not match a valid configuration
+    #[error("Invalid layout: {reason}")]
+    InvalidLayout { reason: &'static str },
+    /// Error when an encoded or binary SnowID has the wrong length
+    #[error("Invalid length {actual}. Expected {expected}")]
+    InvalidLength { expected: usize, actual: usize },
+    /// Error when an encoded SnowID contains a character outside its alphabet
+    #[error("Invalid character '{character}' at position {position}")]
+    InvalidCharacter { character: char, position: usize },
+    /// Error when an encoded SnowID does not fit in 64 bits
+    #[error("Encoded value exceeds the 64-bit range")]
+    EncodingOverflow,
+    /// Error when the check symbol of an encoded SnowID does not match
+    #[error("Checksum mismatch in encoded SnowID")]
+    BadChecksum,
+    /// Error when a custom encoding alphabet is malformed
+    #[error("Invalid alphabet: {reason}")]
+    InvalidAlphabet { reason: &'static str },
+    /// Error when a per-call sequence tag does not fit in its bits
+    #[error("Tag {tag} does not fit in {tag_bits} bits")]
+    InvalidTag { tag: u16, tag_bits: u8 },
+    /// Error when a node ID file cannot be read
+    #[error("Cannot read node ID file {path}: {reason}")]
+    NodeIdFileUnreadable { path: String, reason: String },
+    /// Error when a node ID source does not contain a valid number
+    #[error("Node ID '{value}' is not a valid number")]
+    NodeIdParse { value: String },
+    /// Error when no node ID can be derived from the host
+    #[error("Cannot derive node ID: {reason}")]
+    NodeIdUnavailable { reason: String },
 }
 
 #[cfg(test)]
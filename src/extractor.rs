@@ -1,4 +1,11 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
 use crate::config::SnowIDConfig;
+use crate::encoding;
+use crate::error::SnowIDError;
 
 /// SnowID component extractor
 #[derive(Debug, Copy, Clone)]
@@ -18,6 +25,15 @@ pub fn timestamp(&self, id: u64) -> u64 {
         (id >> self.config.timestamp_shift()) & self.config.timestamp_mask()
     }
 
+    /// Check whether any reserved high bit of a SnowID is set
+    ///
+    /// Reserved bits are always zero in IDs of this configuration, so a set bit
+    /// means the ID comes from a different, wider layout.
+    #[inline]
+    pub fn has_reserved_bits(&self, id: u64) -> bool {
+        (id >> self.config.timestamp_shift()) > self.config.timestamp_mask()
+    }
+
     /// Extract node component from a SnowID
     #[inline]
     pub fn node(&self, id: u64) -> u16 {
@@ -30,10 +46,374 @@ pub fn sequence(&self, id: u64) -> u16 {
         (id & self.config.sequence_mask() as u64) as u16
     }
 
+    /// Split the sequence of an ID from `SnowID::generate_with_high_seq_tag`
+    ///
+    /// # Arguments
+    /// * `id` - Tagged SnowID
+    /// * `tag_bits` - Number of high sequence bits used for the tag
+    ///
+    /// # Returns
+    /// * `(u16, u16)` - Tag and the per-millisecond counter
+    pub fn sequence_tag(&self, id: u64, tag_bits: u8) -> (u16, u16) {
+        let sequence = self.sequence(id) as u32;
+        let counter_bits = self.config.sequence_bits().saturating_sub(tag_bits);
+
+        (
+            (sequence >> counter_bits) as u16,
+            (sequence & ((1 << counter_bits) - 1)) as u16,
+        )
+    }
+
     /// Decompose SnowID into its components: timestamp, node ID, and sequence
     pub fn decompose(&self, id: u64) -> (u64, u16, u16) {
         (self.timestamp(id), self.node(id), self.sequence(id))
     }
+
+    /// Compose a SnowID from its components
+    ///
+    /// Inverse of `decompose` for the same configuration.
+    ///
+    /// # Arguments
+    /// * `timestamp` - Milliseconds since the configured epoch
+    /// * `node` - Node ID
+    /// * `sequence` - Sequence number
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - Composed SnowID or error if a component does not fit
+    pub fn compose(&self, timestamp: u64, node: u16, sequence: u16) -> Result<u64, SnowIDError> {
+        if timestamp > self.config.timestamp_mask() {
+            return Err(SnowIDError::ComponentOutOfRange {
+                component: "timestamp",
+                value: timestamp,
+                max: self.config.timestamp_mask(),
+            });
+        }
+        if node > self.config.max_node_id() {
+            return Err(SnowIDError::InvalidNodeId {
+                node_id: node,
+                max: self.config.max_node_id(),
+            });
+        }
+        if sequence > self.config.max_sequence_id() {
+            return Err(SnowIDError::ComponentOutOfRange {
+                component: "sequence",
+                value: sequence as u64,
+                max: self.config.max_sequence_id() as u64,
+            });
+        }
+
+        Ok((timestamp << self.config.timestamp_shift())
+            | ((node as u64) << self.config.node_shift())
+            | sequence as u64)
+    }
+
+    /// Compute the time bucket a SnowID was created in
+    ///
+    /// Returns `timestamp / granularity` in milliseconds since the epoch, a
+    /// stable bucket number usable e.g. as a per-minute cache namespace.
+    ///
+    /// # Panics
+    /// Panics if granularity is shorter than one millisecond
+    pub fn time_prefix(&self, id: u64, granularity: Duration) -> u64 {
+        let granularity_ms = granularity.as_millis();
+        assert!(
+            granularity_ms > 0,
+            "Granularity must be at least one millisecond"
+        );
+
+        (self.timestamp(id) as u128 / granularity_ms) as u64
+    }
+
+    /// Get the creation time of a SnowID as a chrono `DateTime<Utc>`
+    ///
+    /// # Arguments
+    /// * `id` - SnowID to inspect
+    ///
+    /// # Returns
+    /// * `Option<DateTime<Utc>>` - Creation time, or `None` if the epoch pushes it
+    ///   beyond the range chrono can represent
+    pub fn datetime(&self, id: u64) -> Option<DateTime<Utc>> {
+        let millis = self.timestamp(id).checked_add(self.config.epoch())?;
+        DateTime::<Utc>::from_timestamp_millis(i64::try_from(millis).ok()?)
+    }
+
+    /// Get the creation time of a SnowID as a `time::OffsetDateTime` in UTC
+    ///
+    /// # Arguments
+    /// * `id` - SnowID to inspect
+    ///
+    /// # Returns
+    /// * `Option<time::OffsetDateTime>` - Creation time, or `None` if the epoch pushes
+    ///   it beyond the range the time crate can represent
+    #[cfg(feature = "time")]
+    pub fn offset_datetime(&self, id: u64) -> Option<time::OffsetDateTime> {
+        let millis = self.timestamp(id).checked_add(self.config.epoch())?;
+        time::OffsetDateTime::from_unix_timestamp_nanos(millis as i128 * 1_000_000).ok()
+    }
+
+    /// Format a SnowID with a human-readable creation time prefix
+    ///
+    /// Produces `<ISO-8601 basic UTC time>-<Crockford base32 ID>`, e.g.
+    /// `20240101T120000.250Z-00054SEZ80407`, which is easy to grep in logs and
+    /// still sorts chronologically. If the epoch pushes the creation time out of
+    /// range, the prefix is the raw timestamp field instead, as in
+    /// `invalid@10-...`.
+    pub fn to_debug_string(&self, id: u64) -> String {
+        let time = self.datetime(id).map_or_else(
+            || format!("invalid@{}", self.timestamp(id)),
+            |datetime| datetime.format("%Y%m%dT%H%M%S%.3fZ").to_string(),
+        );
+
+        format!("{}-{}", time, encoding::encode(id))
+    }
+
+    /// Describe everything about a SnowID in a multi-line report
+    ///
+    /// Lists the base32 and decimal forms, the creation time as ISO-8601, the
+    /// components, and the bit layout with the ID's bits laid over it, e.g. for
+    /// command-line tools.
+    pub fn explain(&self, id: u64) -> String {
+        let (timestamp, node, sequence) = self.decompose(id);
+        let time = self.datetime(id).map_or_else(
+            || "out of range".to_string(),
+            |datetime| datetime.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+        );
+
+        let binary = format!("{id:064b}");
+        let mut offset = 0;
+        let mut bits = String::new();
+        for (_, width) in self.config.bit_fields() {
+            if width > 0 {
+                bits.push('[');
+                bits.push_str(&binary[offset..offset + width]);
+                bits.push(']');
+                offset += width;
+            }
+        }
+
+        format!(
+            "SnowID {}\n\
+             decimal:   {id}\n\
+             time:      {time} ({timestamp} ms after epoch)\n\
+             node:      {node}\n\
+             sequence:  {sequence}\n\
+             layout:    {}\n\
+             bits:      {bits}",
+            encoding::encode(id),
+            self.config.bit_diagram(),
+        )
+    }
+
+    /// Describe a SnowID as a JSON object of its components
+    ///
+    /// Produces `{"timestamp_ms":..,"node":..,"sequence":..,"string":".."}`, where
+    /// `timestamp_ms` is relative to the configured epoch and `string` is the
+    /// Crockford encoding. All values are numbers or base32, so no escaping is needed.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self, id: u64) -> String {
+        use std::fmt::Write;
+
+        let (timestamp, node, sequence) = self.decompose(id);
+        let mut json = String::with_capacity(96);
+        write!(
+            json,
+            r#"{{"timestamp_ms":{timestamp},"node":{node},"sequence":{sequence},"string":"{}"}}"#,
+            encoding::encode(id)
+        )
+        .expect("writing to a String never fails");
+        json
+    }
+
+    /// Format a SnowID as a 26-character ULID-compatible string
+    ///
+    /// The 48-bit ULID time field holds the creation time in Unix milliseconds
+    /// and the low 64 bits of the 80-bit randomness field hold the whole
+    /// SnowID; the remaining 16 bits are zero. The result is a valid ULID that
+    /// sorts by creation time, though it carries no randomness.
+    ///
+    /// # Returns
+    /// * `Result<String, SnowIDError>` - ULID string or error if the creation time
+    ///   does not fit the 48-bit ULID time field
+    pub fn to_ulid_string(&self, id: u64) -> Result<String, SnowIDError> {
+        let unix_millis = self.ulid_millis(id)?;
+        Ok(encoding::encode_u128(
+            ((unix_millis as u128) << 80) | id as u128,
+        ))
+    }
+
+    /// Parse a string produced by `to_ulid_string`
+    ///
+    /// Decoding is case-insensitive like `decode`. ULIDs whose time field does
+    /// not match the embedded SnowID under this configuration are rejected.
+    pub fn from_ulid_string(&self, ulid: &str) -> Result<u64, SnowIDError> {
+        let value = encoding::decode_u128(ulid)?;
+        let id = value as u64;
+
+        // Time field followed by the 16 zero bits above the SnowID
+        let expected_high = (self.ulid_millis(id)? as u128) << 16;
+        if value >> 64 != expected_high {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "ULID time field does not match the embedded SnowID",
+            });
+        }
+
+        Ok(id)
+    }
+
+    /// Get the creation time of a SnowID in Unix milliseconds for the ULID time field
+    fn ulid_millis(&self, id: u64) -> Result<u64, SnowIDError> {
+        const MAX_ULID_MILLIS: u64 = (1 << 48) - 1;
+
+        let timestamp = self.timestamp(id);
+        timestamp
+            .checked_add(self.config.epoch())
+            .filter(|&millis| millis <= MAX_ULID_MILLIS)
+            .ok_or(SnowIDError::ComponentOutOfRange {
+                component: "timestamp",
+                value: timestamp,
+                max: MAX_ULID_MILLIS.saturating_sub(self.config.epoch()),
+            })
+    }
+
+    /// Parse a string produced by `to_debug_string`
+    ///
+    /// The time prefix is informational only and ignored; the ID is decoded
+    /// from the base32 part after the last `-`.
+    pub fn from_debug_string(&self, debug: &str) -> Result<u64, SnowIDError> {
+        let encoded = debug.rsplit('-').next().unwrap_or(debug);
+        encoding::decode(encoded)
+    }
+
+    /// Decompose a descending SnowID produced by `SnowID::generate_descending`
+    pub fn decompose_descending(&self, id: u64) -> (u64, u16, u16) {
+        self.decompose(!id)
+    }
+
+    /// Decompose a slice of SnowIDs into a reusable output buffer
+    ///
+    /// `out` is cleared first, so the same buffer can be reused across batches
+    /// without reallocating. Each entry matches `decompose` for the same ID.
+    ///
+    /// Per-ID cost is the same as mapping `decompose` and collecting; the
+    /// saving is the allocation avoided when `out` is reused.
+    pub fn decompose_slice(&self, ids: &[u64], out: &mut Vec<(u64, u16, u16)>) {
+        out.clear();
+        out.reserve(ids.len());
+        out.extend(ids.iter().map(|&id| self.decompose(id)));
+    }
+
+    /// Decompose a slice of SnowIDs into parallel timestamp, node and sequence columns
+    ///
+    /// Struct-of-arrays output for columnar stores and vectorized processing.
+    /// Entry `i` of each column matches `decompose(ids[i])`.
+    ///
+    /// Each column is filled by its own loop, which vectorizes, so this is
+    /// faster per ID than `decompose_slice`.
+    ///
+    /// # Returns
+    /// * `(Vec<u64>, Vec<u16>, Vec<u16>)` - Timestamp, node ID and sequence columns
+    pub fn decompose_columns(&self, ids: &[u64]) -> (Vec<u64>, Vec<u16>, Vec<u16>) {
+        (
+            ids.iter().map(|&id| self.timestamp(id)).collect(),
+            ids.iter().map(|&id| self.node(id)).collect(),
+            ids.iter().map(|&id| self.sequence(id)).collect(),
+        )
+    }
+
+    /// Find sequence numbers missing between IDs of the same millisecond
+    ///
+    /// Expects a sorted slice of IDs from a single node; IDs from several nodes
+    /// interleave and produce meaningless results. Only slots strictly between
+    /// two observed IDs are reported, since the number of IDs a millisecond
+    /// should have held after the last observed one is unknown.
+    ///
+    /// # Returns
+    /// * `Vec<(u64, u16)>` - (timestamp, missing sequence) pairs in ascending order
+    pub fn find_gaps(&self, ids: &[u64]) -> Vec<(u64, u16)> {
+        let mut gaps = Vec::new();
+
+        for pair in ids.windows(2) {
+            let (ts, _, seq) = self.decompose(pair[0]);
+            let (next_ts, _, next_seq) = self.decompose(pair[1]);
+
+            if ts == next_ts && next_seq > seq {
+                gaps.extend((seq + 1..next_seq).map(|missing| (ts, missing)));
+            }
+        }
+
+        gaps
+    }
+
+    /// Check that a slice of SnowIDs is strictly increasing
+    ///
+    /// Compares raw integer values, so duplicates count as violations. Does
+    /// not allocate.
+    ///
+    /// # Returns
+    /// * `Result<(), (usize, u64, u64)>` - Ok if sorted, otherwise the index of the
+    ///   first ID that is not greater than its predecessor, with the predecessor and that ID
+    pub fn check_monotonic(&self, ids: &[u64]) -> Result<(), (usize, u64, u64)> {
+        match ids.windows(2).position(|pair| pair[1] <= pair[0]) {
+            Some(index) => Err((index + 1, ids[index], ids[index + 1])),
+            None => Ok(()),
+        }
+    }
+
+    /// Count how many IDs were generated in each millisecond
+    ///
+    /// Reveals bursts in stored IDs and how close a millisecond came to
+    /// `max_sequence_id`. Assumes every ID shares this extractor's configuration;
+    /// IDs from several nodes are counted together.
+    ///
+    /// # Returns
+    /// * `BTreeMap<u64, u32>` - Number of IDs per timestamp, in ascending timestamp order
+    pub fn count_per_ms(&self, ids: &[u64]) -> BTreeMap<u64, u32> {
+        let mut counts = BTreeMap::new();
+        for &id in ids {
+            *counts.entry(self.timestamp(id)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Collect the node IDs that appear in a set of SnowIDs
+    ///
+    /// Useful for auditing which nodes contributed to stored data: a
+    /// supposedly single-node dataset reporting several nodes points to a
+    /// routing problem.
+    ///
+    /// # Arguments
+    /// * `ids` - SnowIDs to inspect
+    ///
+    /// # Returns
+    /// * `BTreeSet<u16>` - Unique node IDs in ascending order
+    pub fn distinct_nodes(&self, ids: &[u64]) -> BTreeSet<u16> {
+        ids.iter().map(|&id| self.node(id)).collect()
+    }
+
+    /// Compute the time elapsed between the creation of two SnowIDs
+    ///
+    /// The result only has millisecond resolution, as that is the precision
+    /// of the timestamp component. Argument order does not matter.
+    pub fn duration_between(&self, a: u64, b: u64) -> Duration {
+        Duration::from_millis(self.timestamp(a).abs_diff(self.timestamp(b)))
+    }
+
+    /// Count the ID slots from `a` to `b`, ignoring the node field
+    ///
+    /// Computes `(ms_b - ms_a) * (max_sequence + 1) + (seq_b - seq_a)`, the
+    /// number of IDs a single node could have generated in between. Unlike
+    /// subtracting raw IDs, this does not mix in node bits, but it is only
+    /// meaningful when both IDs come from the same node.
+    ///
+    /// # Returns
+    /// * `i128` - Slot distance, negative when `b` precedes `a`
+    pub fn logical_distance(&self, a: u64, b: u64) -> i128 {
+        let slots_per_ms = self.config.max_sequence_id() as i128 + 1;
+        let (ts_a, _, seq_a) = self.decompose(a);
+        let (ts_b, _, seq_b) = self.decompose(b);
+
+        (ts_b as i128 - ts_a as i128) * slots_per_ms + (seq_b as i128 - seq_a as i128)
+    }
 }
 
 #[cfg(test)]
@@ -68,6 +448,247 @@ fn test_decompose() {
         assert_eq!(ext_sequence, sequence);
     }
 
+    #[test]
+    fn test_decompose_slice() {
+        let snowid_gen = SnowID::new(3).unwrap();
+        let ids: Vec<u64> = (0..1000).map(|_| snowid_gen.generate()).collect();
+
+        let mut out = vec![(0, 0, 0); 5];
+        snowid_gen.extract.decompose_slice(&ids, &mut out);
+
+        assert_eq!(out.len(), ids.len());
+        for (id, components) in ids.iter().zip(&out) {
+            assert_eq!(*components, snowid_gen.extract.decompose(*id));
+        }
+
+        snowid_gen.extract.decompose_slice(&[], &mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_decompose_columns() {
+        let snowid_gen = SnowID::new(3).unwrap();
+        let ids: Vec<u64> = (0..1000).map(|_| snowid_gen.generate()).collect();
+
+        let (timestamps, nodes, sequences) = snowid_gen.extract.decompose_columns(&ids);
+        assert_eq!(timestamps.len(), ids.len());
+        for (i, &id) in ids.iter().enumerate() {
+            assert_eq!(
+                (timestamps[i], nodes[i], sequences[i]),
+                snowid_gen.extract.decompose(id)
+            );
+        }
+
+        let (timestamps, nodes, sequences) = snowid_gen.extract.decompose_columns(&[]);
+        assert!(timestamps.is_empty() && nodes.is_empty() && sequences.is_empty());
+    }
+
+    #[test]
+    fn test_debug_string() {
+        let snowid_gen = SnowID::new(1).unwrap();
+
+        // 2024-01-01T12:00:00.250Z relative to the default 2024-01-01 epoch
+        let id = snowid_gen.create_snowid_with_node(43_200_250, 1, 7);
+        let debug = snowid_gen.extract.to_debug_string(id);
+
+        assert_eq!(
+            debug,
+            format!("20240101T120000.250Z-{}", encoding::encode(id))
+        );
+        assert_eq!(snowid_gen.extract.from_debug_string(&debug).unwrap(), id);
+
+        let generated = snowid_gen.generate();
+        let debug = snowid_gen.extract.to_debug_string(generated);
+        assert_eq!(
+            snowid_gen.extract.from_debug_string(&debug).unwrap(),
+            generated
+        );
+        assert!(snowid_gen
+            .extract
+            .from_debug_string("20240101T120000.250Z-")
+            .is_err());
+
+        // A creation time beyond chrono's range shows the raw timestamp
+        let extract = SnowIDExtractor::new(SnowIDConfig::builder().epoch(u64::MAX).build());
+        let id = extract.compose(10, 1, 0).unwrap();
+        let debug = extract.to_debug_string(id);
+        assert_eq!(debug, format!("invalid@10-{}", encoding::encode(id)));
+        assert_eq!(extract.from_debug_string(&debug).unwrap(), id);
+    }
+
+    #[test]
+    fn test_time_prefix() {
+        let snowid_gen = SnowID::new(1).unwrap();
+        let minute = Duration::from_secs(60);
+        let hour = Duration::from_secs(3600);
+
+        let first = snowid_gen.create_snowid_with_node(3_600_000 + 59_999, 1, 0);
+        let second = snowid_gen.create_snowid_with_node(3_600_000 + 60_000, 1, 0);
+        let later = snowid_gen.create_snowid_with_node(7_199_999, 1, 0);
+
+        assert_eq!(snowid_gen.extract.time_prefix(first, minute), 60);
+        assert_eq!(snowid_gen.extract.time_prefix(second, minute), 61);
+        assert_eq!(snowid_gen.extract.time_prefix(first, hour), 1);
+        assert_eq!(snowid_gen.extract.time_prefix(later, hour), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Granularity must be at least one millisecond")]
+    fn test_time_prefix_zero_granularity() {
+        let snowid_gen = SnowID::new(1).unwrap();
+        snowid_gen
+            .extract
+            .time_prefix(snowid_gen.generate(), Duration::from_micros(999));
+    }
+
+    #[test]
+    fn test_find_gaps() {
+        let snowid_gen = SnowID::new(1).unwrap();
+        let id = |ts, seq| snowid_gen.create_snowid_with_node(ts, 1, seq);
+
+        let complete: Vec<u64> = (0..10).map(|seq| id(100, seq)).collect();
+        assert!(snowid_gen.extract.find_gaps(&complete).is_empty());
+
+        let gapped = [
+            id(100, 0),
+            id(100, 1),
+            id(100, 4),
+            id(100, 5),
+            id(101, 0),
+            id(101, 2),
+            id(103, 7),
+        ];
+        assert_eq!(
+            snowid_gen.extract.find_gaps(&gapped),
+            vec![(100, 2), (100, 3), (101, 1)]
+        );
+
+        assert!(snowid_gen.extract.find_gaps(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_count_per_ms() {
+        let snowid_gen = SnowID::new(1).unwrap();
+        let id = |ts, node, seq| snowid_gen.create_snowid_with_node(ts, node, seq);
+
+        let mut ids: Vec<u64> = (0..=4095).map(|seq| id(200, 1, seq)).collect();
+        ids.extend((0..3).map(|seq| id(100, 2, seq)));
+        ids.push(id(100, 1, 0));
+        ids.push(id(300, 1, 0));
+
+        let counts = snowid_gen.extract.count_per_ms(&ids);
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            vec![(100, 4), (200, 4096), (300, 1)]
+        );
+        assert!(snowid_gen.extract.count_per_ms(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_distinct_nodes() {
+        let config = SnowIDConfig::default();
+        let time = config.epoch() + 1_000_000;
+        let ids: Vec<u64> = [7, 300, 7, 1023, 300]
+            .iter()
+            .map(|&node| {
+                SnowID::with_config(node, config)
+                    .unwrap()
+                    .generate_with_time(time)
+                    .unwrap()
+            })
+            .collect();
+
+        let extract = SnowIDExtractor::new(config);
+        assert_eq!(extract.distinct_nodes(&ids), BTreeSet::from([7, 300, 1023]));
+        assert!(extract.distinct_nodes(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_logical_distance() {
+        let snowid_gen = SnowID::new(1).unwrap();
+        let id = |ts, seq| snowid_gen.create_snowid_with_node(ts, 1, seq);
+        let distance = |a, b| snowid_gen.extract.logical_distance(a, b);
+
+        assert_eq!(distance(id(100, 5), id(100, 9)), 4);
+        assert_eq!(distance(id(100, 4095), id(101, 0)), 1);
+        assert_eq!(distance(id(100, 10), id(102, 3)), 2 * 4096 - 7);
+        assert_eq!(distance(id(102, 3), id(100, 10)), -(2 * 4096 - 7));
+        assert_eq!(distance(id(100, 7), id(100, 7)), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_to_json() {
+        let snowid_gen = SnowID::new(1).unwrap();
+        let id = snowid_gen.create_snowid_with_node(43_200_250, 17, 9);
+
+        let json = snowid_gen.extract.to_json(id);
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"timestamp_ms":43200250,"node":17,"sequence":9,"string":"{}"}}"#,
+                encoding::encode(id)
+            )
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["node"], 17);
+        assert_eq!(value["timestamp_ms"], 43_200_250);
+    }
+
+    #[test]
+    fn test_datetime() {
+        let snowid_gen = SnowID::new(1).unwrap();
+        let id = snowid_gen.create_snowid_with_node(43_200_250, 1, 0);
+
+        let expected = DateTime::parse_from_rfc3339("2024-01-01T12:00:00.250Z").unwrap();
+        assert_eq!(snowid_gen.extract.datetime(id), Some(expected.to_utc()));
+
+        let config = SnowIDConfig::builder().epoch(u64::MAX).build();
+        assert_eq!(SnowIDExtractor::new(config).datetime(id), None);
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_offset_datetime() {
+        use time::{Date, Month};
+
+        let instant = Date::from_calendar_date(2024, Month::March, 5)
+            .unwrap()
+            .with_hms_milli(8, 30, 15, 125)
+            .unwrap()
+            .assume_utc();
+        let unix_millis = (instant.unix_timestamp_nanos() / 1_000_000) as u64;
+
+        let snowid_gen = SnowID::new(1).unwrap();
+        let id = snowid_gen.generate_with_time(unix_millis).unwrap();
+        assert_eq!(snowid_gen.extract.offset_datetime(id), Some(instant));
+
+        let config = SnowIDConfig::builder().epoch(u64::MAX).build();
+        assert_eq!(SnowIDExtractor::new(config).offset_datetime(id), None);
+    }
+
+    #[test]
+    fn test_duration_between() {
+        let snowid_gen = SnowID::new(1).unwrap();
+
+        let earlier = snowid_gen.create_snowid_with_node(1_000, 1, 4095);
+        let later = snowid_gen.create_snowid_with_node(61_250, 7, 0);
+
+        assert_eq!(
+            snowid_gen.extract.duration_between(earlier, later),
+            Duration::from_millis(60_250)
+        );
+        assert_eq!(
+            snowid_gen.extract.duration_between(later, earlier),
+            Duration::from_millis(60_250)
+        );
+        assert_eq!(
+            snowid_gen.extract.duration_between(earlier, earlier + 1),
+            Duration::ZERO
+        );
+    }
+
     #[test]
     fn test_component_boundaries() {
         let config = SnowIDConfig::default();
@@ -87,4 +708,116 @@ fn test_component_boundaries() {
         assert_eq!(snowid_gen.extract.node(id), max_node_id);
         assert_eq!(snowid_gen.extract.sequence(id), max_sequence);
     }
+
+    #[test]
+    fn test_check_monotonic() {
+        let snowid_gen = SnowID::new(42).unwrap();
+        let mut ids: Vec<u64> = (0..100).map(|_| snowid_gen.generate()).collect();
+
+        assert_eq!(snowid_gen.extract.check_monotonic(&ids), Ok(()));
+        assert_eq!(snowid_gen.extract.check_monotonic(&[]), Ok(()));
+        assert_eq!(snowid_gen.extract.check_monotonic(&ids[..1]), Ok(()));
+
+        ids.swap(40, 41);
+        assert_eq!(
+            snowid_gen.extract.check_monotonic(&ids),
+            Err((41, ids[40], ids[41]))
+        );
+
+        // Duplicates are not strictly increasing
+        assert_eq!(
+            snowid_gen.extract.check_monotonic(&[1, 2, 2, 3]),
+            Err((2, 2, 2))
+        );
+    }
+
+    #[test]
+    fn test_ulid_string_round_trip() {
+        let snowid_gen = SnowID::new(42).unwrap();
+        let extract = snowid_gen.extract;
+
+        let ids: Vec<u64> = (0..100).map(|_| snowid_gen.generate()).collect();
+        let ulids: Vec<String> = ids
+            .iter()
+            .map(|&id| extract.to_ulid_string(id).unwrap())
+            .collect();
+
+        assert!(ulids.windows(2).all(|pair| pair[0] < pair[1]));
+        for (&id, ulid) in ids.iter().zip(&ulids) {
+            assert_eq!(ulid.len(), crate::ULID_LEN);
+            assert_eq!(extract.from_ulid_string(ulid), Ok(id));
+            assert_eq!(extract.from_ulid_string(&ulid.to_lowercase()), Ok(id));
+        }
+
+        // The time field is the creation time in Unix milliseconds
+        let id = snowid_gen.create_snowid_with_node(43_200_250, 1, 0);
+        let unix_millis = 43_200_250 + snowid_gen.config.epoch();
+        assert_eq!(
+            extract.to_ulid_string(id).unwrap()[..10],
+            encoding::encode_u128((unix_millis as u128) << 80)[..10]
+        );
+    }
+
+    #[test]
+    fn test_ulid_string_rejects_foreign_ulids() {
+        let extract = SnowID::new(42).unwrap().extract;
+
+        assert!(matches!(
+            extract.from_ulid_string("01ARZ3NDEKTSV4RRFFQ69G5FAV"),
+            Err(SnowIDError::InvalidLayout { .. })
+        ));
+        assert_eq!(
+            extract.from_ulid_string("80000000000000000000000000"),
+            Err(SnowIDError::EncodingOverflow)
+        );
+        assert!(matches!(
+            extract.from_ulid_string("0000000000000"),
+            Err(SnowIDError::InvalidLength { expected: 26, .. })
+        ));
+    }
+
+    #[test]
+    fn test_ulid_string_time_out_of_range() {
+        for epoch in [(1 << 48) - 5, u64::MAX] {
+            let config = SnowIDConfig::builder().epoch(epoch).build();
+            let extract = SnowIDExtractor::new(config);
+            let id = extract.compose(10, 1, 0).unwrap();
+
+            assert!(matches!(
+                extract.to_ulid_string(id),
+                Err(SnowIDError::ComponentOutOfRange { value: 10, .. })
+            ));
+            let ulid = encoding::encode_u128(id as u128);
+            assert!(matches!(
+                extract.from_ulid_string(&ulid),
+                Err(SnowIDError::ComponentOutOfRange { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let snowid_gen = SnowID::new(1).unwrap();
+        let id = snowid_gen.create_snowid_with_node(43_200_250, 17, 9);
+
+        let report = snowid_gen.extract.explain(id);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], format!("SnowID {}", encoding::encode(id)));
+        assert_eq!(lines[1], format!("decimal:   {id}"));
+        assert_eq!(
+            lines[2],
+            "time:      2024-01-01T12:00:00.250Z (43200250 ms after epoch)"
+        );
+        assert_eq!(lines[3], "node:      17");
+        assert_eq!(lines[4], "sequence:  9");
+        assert_eq!(
+            lines[5],
+            format!("layout:    {}", snowid_gen.config.bit_diagram())
+        );
+        assert_eq!(
+            lines[6],
+            format!("bits:      [{:042b}][{:010b}][{:012b}]", 43_200_250, 17, 9)
+        );
+    }
 }
@@ -0,0 +1,143 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Number of power-of-two buckets needed to cover every `u64` nanosecond value
+const BUCKETS: usize = 65;
+
+/// One in this many claims that do not wait is timed
+pub(crate) const SAMPLE_EVERY: u64 = 64;
+
+/// Summary of how long ID generation has taken, including time spent waiting
+///
+/// Percentiles are bucketed to the next power of two nanoseconds, so they are
+/// upper bounds within a factor of two. `max` is exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LatencyStats {
+    /// Median generation latency
+    pub p50: Duration,
+    /// 99th percentile generation latency
+    pub p99: Duration,
+    /// Slowest generation observed
+    pub max: Duration,
+}
+
+/// Timer for a single claim, started only if the claim is sampled or waits
+#[derive(Debug)]
+pub(crate) struct LatencyTimer {
+    started: Instant,
+    waited: bool,
+}
+
+impl LatencyTimer {
+    pub(crate) fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            waited: false,
+        }
+    }
+
+    /// Mark that the claim has to wait for the next millisecond
+    pub(crate) fn wait(&mut self) {
+        self.waited = true;
+    }
+}
+
+/// Lock-free log2 histogram of generation latencies
+#[derive(Debug)]
+pub(crate) struct LatencyHistogram {
+    buckets: [AtomicU64; BUCKETS],
+    max_nanos: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub(crate) fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            max_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a finished claim
+    ///
+    /// Every claim that waited is recorded as is; a sampled claim that did
+    /// not wait stands in for the `SAMPLE_EVERY` claims around it.
+    pub(crate) fn finish(&self, timer: LatencyTimer) {
+        let weight = if timer.waited { 1 } else { SAMPLE_EVERY };
+        self.record_weighted(timer.started.elapsed(), weight);
+    }
+
+    /// Record a single generation latency
+    #[cfg(test)]
+    pub(crate) fn record(&self, elapsed: Duration) {
+        self.record_weighted(elapsed, 1);
+    }
+
+    fn record_weighted(&self, elapsed: Duration, weight: u64) {
+        let nanos = elapsed.as_nanos().min(u64::MAX as u128) as u64;
+        let bucket = (u64::BITS - nanos.leading_zeros()) as usize;
+
+        self.buckets[bucket].fetch_add(weight, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Summarize the recorded latencies
+    pub(crate) fn stats(&self) -> LatencyStats {
+        let counts: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect();
+        let max = self.max_nanos.load(Ordering::Relaxed);
+
+        LatencyStats {
+            p50: Duration::from_nanos(percentile(&counts, 50).min(max)),
+            p99: Duration::from_nanos(percentile(&counts, 99).min(max)),
+            max: Duration::from_nanos(max),
+        }
+    }
+}
+
+/// Get the upper bound of the bucket holding the given percentile
+fn percentile(counts: &[u64], percent: u64) -> u64 {
+    let total: u64 = counts.iter().sum();
+    let rank = (total * percent).div_ceil(100).max(1);
+
+    let mut seen = 0;
+    for (bucket, &count) in counts.iter().enumerate() {
+        seen += count;
+        if seen >= rank {
+            return match bucket {
+                0 => 0,
+                64 => u64::MAX,
+                bucket => (1 << bucket) - 1,
+            };
+        }
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_histogram() {
+        assert_eq!(LatencyHistogram::new().stats(), LatencyStats::default());
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let histogram = LatencyHistogram::new();
+        for _ in 0..98 {
+            histogram.record(Duration::from_nanos(100));
+        }
+        histogram.record(Duration::from_micros(50));
+        histogram.record(Duration::from_millis(3));
+
+        let stats = histogram.stats();
+        assert_eq!(stats.p50, Duration::from_nanos(127));
+        assert_eq!(stats.p99, Duration::from_nanos(65_535));
+        assert_eq!(stats.max, Duration::from_millis(3));
+    }
+}
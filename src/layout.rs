@@ -0,0 +1,196 @@
+use crate::{SnowID, SnowIDConfig, SnowIDError};
+
+/// Shifts and masks of a bit layout, computable in const context
+///
+/// Lets downstream `const` items and macros embed a layout without runtime cost.
+/// Invalid layouts panic, which fails compilation when evaluated in a `const`.
+///
+/// ```
+/// use snowid::BitConfig;
+///
+/// const BITS: BitConfig = BitConfig::for_layout(42, 10, 12);
+/// assert_eq!(BITS.node_mask, 0x3FF);
+/// ```
+///
+/// ```compile_fail
+/// use snowid::BitConfig;
+///
+/// // 42 + 12 + 12 = 66 bits
+/// const BITS: BitConfig = BitConfig::for_layout(42, 12, 12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitConfig {
+    /// Bits to shift right to reach the timestamp
+    pub timestamp_shift: u8,
+    /// Bits to shift right to reach the node ID
+    pub node_shift: u8,
+    /// Mask of the timestamp after shifting
+    pub timestamp_mask: u64,
+    /// Mask of the node ID after shifting
+    pub node_mask: u16,
+    /// Mask of the sequence
+    pub sequence_mask: u16,
+}
+
+impl BitConfig {
+    /// Compute shifts and masks for a layout
+    ///
+    /// # Arguments
+    /// * `timestamp_bits` - Width of the timestamp field, must be 42
+    /// * `node_bits` - Width of the node ID field, 6-16
+    /// * `sequence_bits` - Width of the sequence field; all three must add up to 64
+    ///
+    /// # Panics
+    /// Panics if the layout is not supported by `SnowIDConfig`
+    pub const fn for_layout(timestamp_bits: u8, node_bits: u8, sequence_bits: u8) -> Self {
+        assert!(
+            timestamp_bits as u32 == SnowID::TIMESTAMP_BITS
+                && node_bits >= 6
+                && node_bits <= 16
+                && node_bits + sequence_bits == SnowID::TOTAL_NODE_AND_SEQUENCE_BITS,
+            "Layout must be 42 timestamp bits, 6-16 node bits and 64 bits in total"
+        );
+
+        Self {
+            timestamp_shift: node_bits + sequence_bits,
+            node_shift: sequence_bits,
+            timestamp_mask: (1 << timestamp_bits) - 1,
+            node_mask: ((1u32 << node_bits) - 1) as u16,
+            sequence_mask: ((1u32 << sequence_bits) - 1) as u16,
+        }
+    }
+}
+
+/// Bit layout checked at compile time
+///
+/// `T`, `N` and `S` are the timestamp, node and sequence widths. They must add
+/// up to 64, with the 42-bit timestamp and 6-16 node bits required by
+/// `SnowIDConfig`; any other split fails to compile once the layout is used.
+///
+/// ```
+/// use snowid::FixedLayout;
+///
+/// type Layout = FixedLayout<42, 12, 10>;
+///
+/// let generator = Layout::generator(7).unwrap();
+/// let id = generator.generate();
+/// assert_eq!(Layout::node(id), 7);
+/// ```
+///
+/// ```compile_fail
+/// use snowid::FixedLayout;
+///
+/// // 42 + 12 + 12 = 66 bits
+/// let node = FixedLayout::<42, 12, 12>::node(0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FixedLayout<const T: u8, const N: u8, const S: u8>;
+
+impl<const T: u8, const N: u8, const S: u8> FixedLayout<T, N, S> {
+    const VALID: () = assert!(
+        T as u32 == SnowID::TIMESTAMP_BITS
+            && N >= 6
+            && N <= 16
+            && N + S == SnowID::TOTAL_NODE_AND_SEQUENCE_BITS,
+        "Layout must be 42 timestamp bits, 6-16 node bits and 64 bits in total"
+    );
+
+    /// Extract timestamp component from a SnowID
+    #[inline]
+    pub const fn timestamp(id: u64) -> u64 {
+        let () = Self::VALID;
+        (id >> (N + S)) & ((1u64 << T) - 1)
+    }
+
+    /// Extract node component from a SnowID
+    #[inline]
+    pub const fn node(id: u64) -> u16 {
+        let () = Self::VALID;
+        ((id >> S) & ((1u64 << N) - 1)) as u16
+    }
+
+    /// Extract sequence component from a SnowID
+    #[inline]
+    pub const fn sequence(id: u64) -> u16 {
+        let () = Self::VALID;
+        (id & ((1u64 << S) - 1)) as u16
+    }
+
+    /// Decompose SnowID into its components: timestamp, node ID, and sequence
+    #[inline]
+    pub const fn decompose(id: u64) -> (u64, u16, u16) {
+        (Self::timestamp(id), Self::node(id), Self::sequence(id))
+    }
+
+    /// Get the runtime configuration matching this layout
+    ///
+    /// # Arguments
+    /// * `epoch` - Custom epoch timestamp in milliseconds since Unix epoch
+    pub fn config(epoch: u64) -> SnowIDConfig {
+        let () = Self::VALID;
+        SnowIDConfig::builder().node_bits(N).epoch(epoch).build()
+    }
+
+    /// Create a generator using this layout and the default epoch
+    ///
+    /// # Arguments
+    /// * `node_id` - Node ID to use in generated IDs
+    ///
+    /// # Returns
+    /// * `Result<SnowID, SnowIDError>` - New SnowID generator or error if node_id is invalid
+    pub fn generator(node_id: u16) -> Result<SnowID, SnowIDError> {
+        SnowID::with_config(node_id, Self::config(SnowIDConfig::default().epoch()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_runtime_extraction() {
+        type Layout = FixedLayout<42, 12, 10>;
+
+        let generator = Layout::generator(4095).unwrap();
+        assert_eq!(generator.config.node_bits(), 12);
+
+        for _ in 0..100 {
+            let id = generator.generate();
+            assert_eq!(Layout::decompose(id), generator.extract.decompose(id));
+        }
+    }
+
+    #[test]
+    fn test_const_extraction() {
+        type Layout = FixedLayout<42, 10, 12>;
+        const ID: u64 = (1234 << 22) | (5 << 12) | 6;
+        const PARTS: (u64, u16, u16) = Layout::decompose(ID);
+        assert_eq!(PARTS, (1234, 5, 6));
+    }
+
+    #[test]
+    fn test_bit_config_matches_runtime() {
+        const _: BitConfig = BitConfig::for_layout(42, 10, 12);
+
+        for node_bits in 6..=16 {
+            let config = SnowIDConfig::builder().node_bits(node_bits).build();
+            let bits = BitConfig::for_layout(42, node_bits, 22 - node_bits);
+            assert_eq!(bits.timestamp_shift, config.timestamp_shift());
+            assert_eq!(bits.node_shift, config.node_shift());
+            assert_eq!(bits.timestamp_mask, config.timestamp_mask());
+            assert_eq!(bits.node_mask, config.node_mask());
+            assert_eq!(bits.sequence_mask, config.sequence_mask());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Layout must be 42 timestamp bits")]
+    fn test_bit_config_invalid_layout() {
+        BitConfig::for_layout(42, 20, 2);
+    }
+
+    #[test]
+    fn test_invalid_node_id() {
+        assert!(FixedLayout::<42, 6, 16>::generator(64).is_err());
+    }
+}
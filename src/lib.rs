@@ -1,33 +1,90 @@
+use std::fs;
+use std::path::Path;
 use std::sync::atomic::{AtomicU16, AtomicU64, Ordering};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+mod allocator;
+mod atomic;
+mod comparator;
+mod composite;
 mod config;
+mod encoding;
 mod error;
 mod extractor;
+#[cfg(feature = "latency_stats")]
+mod latency;
+mod layout;
+mod local;
+#[cfg(feature = "prometheus")]
+mod metrics;
+pub mod registry;
+mod sharded;
+mod spare;
+#[cfg(feature = "stream")]
+mod stream;
 
 #[cfg(test)]
 mod tests;
 
-pub use config::SnowIDConfig;
+pub use allocator::NodeIdAllocator;
+pub use atomic::AtomicSnowID;
+pub use comparator::CrossConfigComparator;
+pub use composite::CompositeKey;
+pub use config::{ConfigDescriptor, GeneratorDescriptor, SequencePolicy, SnowIDConfig};
+pub use encoding::{
+    decode, decode_checked, decode_padded, decode_with, decode_with_symbols, encode,
+    encode_checked, encode_padded, encode_with, encode_with_symbols, from_bytes, from_sort_key,
+    from_sortable_i64, kafka_partition, to_bytes, to_sort_key, to_sortable_i64, Alphabet,
+    CustomAlphabet, ENCODED_LEN, ULID_LEN,
+};
 pub use error::SnowIDError;
 pub use extractor::SnowIDExtractor;
+#[cfg(feature = "latency_stats")]
+pub use latency::LatencyStats;
+pub use layout::{BitConfig, FixedLayout};
+pub use local::LocalSnowID;
+pub use sharded::ShardedSnowID;
 
 /// Main ID generator
+///
+/// The system clock is smoothed so the timestamp part of generated IDs never
+/// goes backwards: if `SystemTime` steps back, as some platforms do around a
+/// leap second, IDs keep using the last timestamp until the clock catches up.
+/// This makes the generator safe across leap seconds and smeared clocks; a
+/// backward step longer than the sequence range can absorb blocks `generate`
+/// (or fails `try_generate` under `SequencePolicy::Error`) until then.
 #[derive(Debug)]
 pub struct SnowID {
-    node_id: u16,
+    node_id: AtomicU16,
     pub config: SnowIDConfig,
     pub extract: SnowIDExtractor,
-    last_timestamp: AtomicU64,
-    sequence: AtomicU16,
+    // Last used timestamp and sequence counter, packed as `timestamp << 16 | counter`
+    // so a millisecond rollover and the sequence reset are one atomic transition.
+    // Bits 58 and up hold the tag width the millisecond was claimed with, 0 if untagged
+    state: AtomicU64,
+    // Sub-range of the sequence field owned by this generator: values are
+    // `sequence_base + counter` with the counter running up to `sequence_limit`
+    sequence_base: u16,
+    sequence_limit: u16,
+    created_at: SystemTime,
+    #[cfg(feature = "latency_stats")]
+    latency: latency::LatencyHistogram,
+    #[cfg(feature = "prometheus")]
+    metrics: metrics::Metrics,
+    spares: Option<spare::SpareNodes>,
 }
 
 impl SnowID {
     pub const TIMESTAMP_BITS: u32 = 42;
     pub const TOTAL_NODE_AND_SEQUENCE_BITS: u8 = 22;
     const MAX_BACKOFF_MS: u64 = 100;
+    const SPARE_NODE_THRESHOLD_MS: u64 = 3;
+    const STATE_FORMAT_VERSION: u8 = 1;
+    const STATE_LEN: usize = 22;
+    const STATE_COUNTER_BITS: u32 = 16;
+    const STATE_TAG_SHIFT: u32 = Self::STATE_COUNTER_BITS + Self::TIMESTAMP_BITS;
 
     /// Create a new SnowID generator with default configuration
     ///
@@ -50,74 +107,1260 @@ pub fn new(node_id: u16) -> Result<Self, SnowIDError> {
     ///
     /// # Returns
     /// * `Result<SnowID, Error>` - New SnowID generator or error if node_id is invalid
+    ///   or the current time is past the timestamp range
     pub fn with_config(node_id: u16, config: SnowIDConfig) -> Result<Self, SnowIDError> {
         if node_id > config.max_node_id() {
             return Err(SnowIDError::InvalidNodeId {
                 node_id,
-                max: (1 << config.node_bits()) - 1,
+                max: config.max_node_id(),
             });
         }
+        config.check_clock_in_range()?;
 
         Ok(Self {
-            node_id,
+            node_id: AtomicU16::new(node_id),
             extract: SnowIDExtractor::new(config),
             config,
-            last_timestamp: AtomicU64::new(0),
-            sequence: AtomicU16::new(0),
+            state: AtomicU64::new(0),
+            sequence_base: config.sequence_reset_base(),
+            sequence_limit: config.max_sequence_id() - config.sequence_reset_base(),
+            created_at: SystemTime::now(),
+            #[cfg(feature = "latency_stats")]
+            latency: latency::LatencyHistogram::new(),
+            #[cfg(feature = "prometheus")]
+            metrics: metrics::Metrics::new(node_id),
+            spares: None,
         })
     }
 
+    /// Create a new SnowID generator whose node ID is derived from the process ID
+    ///
+    /// Keeps co-located processes that would otherwise all default to node 0
+    /// apart. The node ID is `pid mod max_nodes`, so two processes collide when
+    /// their PIDs differ by a multiple of `config.max_nodes()`; with many
+    /// processes per host, see `SnowIDConfig::birthday_collision_probability`.
+    /// Do not rely on this across hosts, where PIDs overlap freely.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Custom configuration
+    ///
+    /// # Returns
+    /// * `SnowID` - New SnowID generator
+    ///
+    /// # Panics
+    /// Panics if the current time is past the timestamp range of `config`
+    pub fn from_pid(config: SnowIDConfig) -> Self {
+        let node_id = Self::node_id_for_pid(std::process::id(), &config);
+        Self::with_config(node_id, config).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Create a new SnowID generator from a published descriptor
+    ///
+    /// Rebuilds a generator with the node ID and layout of the one that
+    /// produced the descriptor, typically to decode its IDs. The sequence
+    /// policy is not part of the descriptor and uses the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - Descriptor from `SnowID::descriptor`
+    ///
+    /// # Returns
+    /// * `Result<SnowID, SnowIDError>` - New SnowID generator or error if the
+    ///   layout or node ID is invalid
+    pub fn from_descriptor(descriptor: &GeneratorDescriptor) -> Result<Self, SnowIDError> {
+        let config = SnowIDConfig::try_from(descriptor.config_descriptor)?;
+        Self::with_config(descriptor.node_id, config)
+    }
+
+    /// Restore a SnowID generator from a checkpoint written by `SnowID::checkpoint`
+    ///
+    /// The restored generator treats the checkpointed millisecond as used up,
+    /// so its first ID lies in a later millisecond and every ID it produces is
+    /// greater than those issued before the checkpoint. Like `from_descriptor`,
+    /// the sequence policy uses the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Checkpoint bytes
+    ///
+    /// # Returns
+    /// * `Result<SnowID, SnowIDError>` - Restored SnowID generator or error if the
+    ///   bytes are truncated, use an unknown format version, or describe an invalid
+    ///   layout, node ID or timestamp
+    pub fn restore(bytes: &[u8]) -> Result<Self, SnowIDError> {
+        if bytes.len() != Self::STATE_LEN {
+            return Err(SnowIDError::InvalidLength {
+                expected: Self::STATE_LEN,
+                actual: bytes.len(),
+            });
+        }
+        if bytes[0] != Self::STATE_FORMAT_VERSION {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "unsupported state format version",
+            });
+        }
+
+        let u64_at = |at: usize| u64::from_be_bytes(bytes[at..at + 8].try_into().unwrap());
+        let descriptor = GeneratorDescriptor {
+            node_id: u16::from_be_bytes([bytes[1], bytes[2]]),
+            config_descriptor: ConfigDescriptor {
+                timestamp_bits: bytes[3],
+                node_bits: bytes[4],
+                sequence_bits: bytes[5],
+                epoch: u64_at(6),
+            },
+        };
+        let generator = Self::from_descriptor(&descriptor)?;
+
+        let last_timestamp = u64_at(14);
+        if last_timestamp > generator.config.timestamp_mask() {
+            return Err(SnowIDError::ComponentOutOfRange {
+                component: "timestamp",
+                value: last_timestamp,
+                max: generator.config.timestamp_mask(),
+            });
+        }
+        generator.store_state(last_timestamp, generator.sequence_limit);
+
+        Ok(generator)
+    }
+
+    /// Create a new SnowID generator with a node ID claimed from a pool
+    ///
+    /// Gives every generator spawned from the same allocator a distinct node ID.
+    /// The ID stays claimed after the generator is dropped; hand it back with
+    /// `allocator.release(generator.node_id())` once it is retired.
+    ///
+    /// # Arguments
+    ///
+    /// * `allocator` - Pool to claim the node ID from
+    /// * `config` - Custom configuration
+    ///
+    /// # Returns
+    /// * `Result<SnowID, SnowIDError>` - New SnowID generator or error if the pool
+    ///   is exhausted or the claimed ID does not fit the configuration
+    pub fn from_allocator(
+        allocator: &NodeIdAllocator,
+        config: SnowIDConfig,
+    ) -> Result<Self, SnowIDError> {
+        let node_id = allocator
+            .allocate()
+            .ok_or_else(|| SnowIDError::NodeIdUnavailable {
+                reason: "node ID pool exhausted".to_string(),
+            })?;
+
+        Self::with_config(node_id, config).inspect_err(|_| allocator.release(node_id))
+    }
+
+    /// Create a new SnowID generator that borrows spare node IDs under sustained load
+    ///
+    /// Once the primary node has exhausted its sequence in three consecutive
+    /// milliseconds, `generate` and `try_generate` keep issuing IDs in the same
+    /// millisecond from the spares, in order, instead of waiting. As soon as a
+    /// millisecond passes without exhaustion, IDs come from the primary node
+    /// again. IDs stay unique as long as no other generator uses any of these
+    /// node IDs.
+    ///
+    /// The trade-off is ordering: within a millisecond, IDs from a spare sort by
+    /// node ID rather than by generation order, so they may sort before primary
+    /// IDs generated earlier in that millisecond. Across milliseconds IDs remain
+    /// increasing.
+    ///
+    /// # Arguments
+    ///
+    /// * `primary` - Node ID used whenever the sequence is not under pressure
+    /// * `spares` - Distinct node IDs owned by this generator for overflow
+    /// * `config` - Custom configuration
+    ///
+    /// # Returns
+    /// * `Result<SnowID, SnowIDError>` - New SnowID generator or error if a node ID is
+    ///   invalid or repeated
+    pub fn with_spare_nodes(
+        primary: u16,
+        spares: &[u16],
+        config: SnowIDConfig,
+    ) -> Result<Self, SnowIDError> {
+        let mut generator = Self::with_config(primary, config)?;
+
+        if let Some(&node_id) = spares.iter().find(|&&node| node > config.max_node_id()) {
+            return Err(SnowIDError::InvalidNodeId {
+                node_id,
+                max: config.max_node_id(),
+            });
+        }
+        let mut nodes: Vec<u16> = spares.iter().copied().chain([primary]).collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+        if nodes.len() != spares.len() + 1 {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "spare nodes must be distinct from each other and the primary",
+            });
+        }
+
+        if !spares.is_empty() {
+            generator.spares = Some(spare::SpareNodes::new(
+                spares,
+                generator.sequence_limit as u64 + 1,
+                Self::SPARE_NODE_THRESHOLD_MS,
+            ));
+        }
+        Ok(generator)
+    }
+
+    /// Create a new SnowID generator with the node ID read from a file
+    ///
+    /// For deployment tooling that writes the assigned node ID to a file.
+    /// Surrounding whitespace, such as a trailing newline, is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - File containing the node ID as a decimal number
+    /// * `config` - Custom configuration
+    ///
+    /// # Returns
+    /// * `Result<SnowID, Error>` - New SnowID generator or error if the file cannot be
+    ///   read, does not hold a number, or the node ID is out of range
+    pub fn from_file(path: impl AsRef<Path>, config: SnowIDConfig) -> Result<Self, SnowIDError> {
+        let path = path.as_ref();
+        let contents =
+            fs::read_to_string(path).map_err(|error| SnowIDError::NodeIdFileUnreadable {
+                path: path.display().to_string(),
+                reason: error.to_string(),
+            })?;
+
+        let value = contents.trim();
+        let node_id = value.parse().map_err(|_| SnowIDError::NodeIdParse {
+            value: value.to_string(),
+        })?;
+
+        Self::with_config(node_id, config)
+    }
+
+    /// Create a new SnowID generator whose node ID comes from the host's IPv4 address
+    ///
+    /// Takes the low `octets` bytes of the primary IPv4 address (the one used
+    /// for outbound traffic; no packets are sent) and masks them to the node
+    /// bits. Suits flat networks where those octets are unique per host; any
+    /// two hosts whose masked low bits match will collide.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Custom configuration
+    /// * `octets` - Number of low address bytes to use, 1-4
+    ///
+    /// # Returns
+    /// * `Result<SnowID, Error>` - New SnowID generator or error if `octets` is out of
+    ///   range or no IPv4 address is available
+    #[cfg(feature = "net")]
+    pub fn from_ipv4_suffix(config: SnowIDConfig, octets: u8) -> Result<Self, SnowIDError> {
+        let unavailable = |error: std::io::Error| SnowIDError::NodeIdUnavailable {
+            reason: error.to_string(),
+        };
+
+        let socket = std::net::UdpSocket::bind("0.0.0.0:0").map_err(unavailable)?;
+        socket.connect("192.0.2.1:9").map_err(unavailable)?;
+        let std::net::IpAddr::V4(addr) = socket.local_addr().map_err(unavailable)?.ip() else {
+            return Err(SnowIDError::NodeIdUnavailable {
+                reason: "no IPv4 address".to_string(),
+            });
+        };
+
+        let node_id = Self::node_id_for_ipv4(addr, octets, &config)?;
+        Self::with_config(node_id, config)
+    }
+
     /// Generate a new SnowID
     ///
+    /// Waits for the next millisecond when the sequence is exhausted. As this
+    /// cannot fail, `SequencePolicy::Error` behaves like `RollToNextMs` here;
+    /// use `try_generate` to observe exhaustion.
+    ///
+    /// Never returns 0: the clock is only read after the epoch, so the
+    /// timestamp part of every generated ID is at least 1.
+    ///
     /// # Returns
     /// * `u64` - New SnowID value
     pub fn generate(&self) -> u64 {
+        if let Some(spares) = &self.spares {
+            return self
+                .generate_with_spares(spares, self.waiting_policy())
+                .expect("waiting policies never fail");
+        }
+
+        let (timestamp, node_id, sequence) = self.next_timestamp_and_sequence();
+        self.create_snowid(timestamp, node_id, sequence)
+    }
+
+    /// Generate a new SnowID and raise a high-water mark to it
+    ///
+    /// IDs from one generator only grow, so the mark advances on every call
+    /// unless another generator sharing it is ahead.
+    ///
+    /// # Arguments
+    /// * `hwm` - High-water mark to update
+    ///
+    /// # Returns
+    /// * `u64` - New SnowID value
+    pub fn generate_and_mark(&self, hwm: &AtomicSnowID) -> u64 {
+        let id = self.generate();
+        hwm.fetch_max(id);
+        id
+    }
+
+    /// Generate a new SnowID, honoring the configured sequence policy
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - New SnowID value or error if the sequence is
+    ///   exhausted under `SequencePolicy::Error`
+    pub fn try_generate(&self) -> Result<u64, SnowIDError> {
+        if let Some(spares) = &self.spares {
+            return self.generate_with_spares(spares, self.config.sequence_policy());
+        }
+
+        let (timestamp, node_id, sequence) = self.claim_timestamp_and_sequence(
+            self.config.sequence_policy(),
+            self.sequence_limit,
+            0,
+        )?;
+        Ok(self.create_snowid(timestamp, node_id, sequence))
+    }
+
+    /// Generate a new SnowID with a caller tag in the high bits of its sequence
+    ///
+    /// Splits the sequence field for this call only: the top `tag_bits` bits
+    /// carry `tag` and the rest count within the millisecond, so fewer IDs fit
+    /// per millisecond. Tagged and untagged IDs share one counter, so each
+    /// millisecond keeps the tag width of its first ID: a call with another
+    /// `tag_bits` in the same millisecond fails, and untagged calls wait for the
+    /// next one. Use one tag width per generator.
+    ///
+    /// # Arguments
+    /// * `tag` - Value stored in the high sequence bits
+    /// * `tag_bits` - Number of high sequence bits reserved for the tag
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - New SnowID value or error if the tag does not fit,
+    ///   the current millisecond was claimed with another tag width, or the
+    ///   sequence is exhausted under `SequencePolicy::Error`
+    pub fn generate_with_high_seq_tag(&self, tag: u16, tag_bits: u8) -> Result<u64, SnowIDError> {
+        if self.sequence_limit != self.config.max_sequence_id() {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "sequence tags need the full sequence range",
+            });
+        }
+
+        let counter_bits = match self.config.sequence_bits().checked_sub(tag_bits) {
+            Some(bits) if bits > 0 => bits,
+            _ => {
+                return Err(SnowIDError::InvalidLayout {
+                    reason: "tag bits must leave at least one sequence bit",
+                })
+            }
+        };
+        if (tag as u32) >> tag_bits != 0 {
+            return Err(SnowIDError::InvalidTag { tag, tag_bits });
+        }
+
+        let max_counter = ((1u32 << counter_bits) - 1) as u16;
+        let (timestamp, node_id, counter) = self.claim_timestamp_and_sequence(
+            self.config.sequence_policy(),
+            max_counter,
+            tag_bits,
+        )?;
+        Ok(self.create_snowid(timestamp, node_id, (tag << counter_bits) | counter))
+    }
+
+    /// Generate a new SnowID in descending form, where newer IDs are smaller
+    ///
+    /// Returns the bitwise complement of a regular SnowID (`u64::MAX - id`) for
+    /// stores optimized for newest-first scans. Descending IDs are not comparable
+    /// with regular ones; decode them with `extract.decompose_descending`.
+    ///
+    /// # Returns
+    /// * `u64` - New descending SnowID value
+    pub fn generate_descending(&self) -> u64 {
+        !self.generate()
+    }
+
+    /// Generate a new SnowID and write its Crockford base32 encoding into `buf`
+    ///
+    /// The encoding is always exactly `ENCODED_LEN` (13) bytes, so no heap
+    /// allocation is needed for the common "generate and format" pattern.
+    ///
+    /// # Arguments
+    /// * `buf` - Buffer receiving the encoded SnowID
+    ///
+    /// # Returns
+    /// * `&str` - View of the encoded SnowID inside `buf`
+    pub fn generate_into<'a>(&self, buf: &'a mut [u8; ENCODED_LEN]) -> &'a str {
+        encoding::encode_into(self.generate(), buf)
+    }
+
+    /// Generate a new SnowID together with its Crockford base32 encoding
+    ///
+    /// For code that stores the numeric ID and also logs or returns its string
+    /// form. The string equals `encode(id)`.
+    ///
+    /// # Returns
+    /// * `(u64, String)` - New SnowID value and its encoding
+    pub fn generate_with_string(&self) -> (u64, String) {
+        let id = self.generate();
+        (id, encoding::encode(id))
+    }
+
+    /// Generate a new SnowID together with its components
+    ///
+    /// Avoids decoding the ID again when its parts are needed right away,
+    /// e.g. for logging the timestamp.
+    ///
+    /// # Returns
+    /// * `(u64, (u64, u16, u16))` - New SnowID value and its (timestamp, node ID, sequence),
+    ///   identical to what `extract.decompose` would return
+    pub fn generate_with_components(&self) -> (u64, (u64, u16, u16)) {
+        let (timestamp, node_id, counter) = self.next_timestamp_and_sequence();
+        let sequence = self.sequence_base + counter;
+        let id = self.create_snowid_with_node(timestamp, node_id, sequence);
+
+        let components = (
+            timestamp & self.config.timestamp_mask(),
+            node_id & self.config.node_mask(),
+            sequence & self.config.sequence_mask(),
+        );
+
+        (id, components)
+    }
+
+    /// Generate a new SnowID that is guaranteed to land in the given shard
+    ///
+    /// Shards are derived from node IDs as `node_id % num_shards`. The generator
+    /// never borrows another node ID, since that node may be owned by a different
+    /// generator; instead the shard is validated against its own node ID.
+    ///
+    /// # Arguments
+    /// * `shard` - Shard the ID must belong to
+    /// * `num_shards` - Total number of shards
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - New SnowID value or error if this node is not in the shard
+    pub fn generate_for_shard(&self, shard: u16, num_shards: u16) -> Result<u64, SnowIDError> {
+        let node_id = self.node_id();
+        if num_shards == 0 || node_id % num_shards != shard {
+            return Err(SnowIDError::ShardMismatch {
+                node_id,
+                shard,
+                num_shards,
+            });
+        }
+
+        Ok(self.generate())
+    }
+
+    /// Advance the sequence by `n` slots without emitting IDs
+    ///
+    /// Claims the slots like `generate` would, at the current millisecond, so an
+    /// external allocator can hand out a matching block. If the skip runs past
+    /// the maximum sequence, the rest of the millisecond is reserved and the next
+    /// ID comes from the following millisecond; the overflow is not carried over.
+    ///
+    /// # Arguments
+    /// * `n` - Number of sequence slots to leave unused
+    pub fn skip(&self, n: u16) {
+        if n == 0 {
+            return;
+        }
+
+        self.next_timestamp_and_sequence();
+
+        let max_sequence = self.sequence_limit as u32;
+        let _ = self
+            .state
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                let (last_ts, sequence) = Self::unpack_state(current);
+                let sequence = sequence as u32;
+                let skipped = (sequence + n as u32 - 1).min(max_sequence.max(sequence));
+                let tag_bits = Self::state_tag_bits(current);
+                Some(Self::pack_tagged_state(last_ts, skipped as u16, tag_bits))
+            });
+    }
+
+    /// Split this generator into two that can never produce the same ID
+    ///
+    /// Both keep the node ID and configuration, and each owns one half of this
+    /// generator's sequence range, so each fits half as many IDs per
+    /// millisecond. IDs stay monotonic per generator. Both halves start at the
+    /// millisecond after the last one used here, so they cannot repeat IDs
+    /// already issued. Halves can be split again. Sequence tags are not
+    /// available on split generators.
+    ///
+    /// # Returns
+    /// * `(SnowID, SnowID)` - Generators owning the lower and upper half of the sequence range
+    ///
+    /// # Panics
+    /// Panics if the sequence range has a single value left and cannot be halved
+    pub fn split_sequence_space(self) -> (Self, Self) {
+        let range_len = self.sequence_limit as u32 + 1;
+        let half = (range_len / 2) as u16;
+        assert!(half > 0, "Sequence range is too small to split");
+
+        let (last_timestamp, _) = self.load_state();
+        let half_generator = |base: u16| {
+            let mut generator = Self::with_config(self.node_id(), self.config)
+                .expect("node ID was validated on construction");
+            generator.sequence_base = base;
+            generator.sequence_limit = half - 1;
+            // Mark the current millisecond as used up
+            generator.store_state(last_timestamp, half - 1);
+            generator
+        };
+
+        (
+            half_generator(self.sequence_base),
+            half_generator(self.sequence_base + half),
+        )
+    }
+
+    /// Generate up to `n` SnowIDs, stopping once the deadline has passed
+    ///
+    /// Sequence exhaustion is handled like `generate`, by waiting for the next
+    /// millisecond, so a single wait may overrun the deadline slightly.
+    ///
+    /// # Arguments
+    /// * `n` - Maximum number of IDs to generate
+    /// * `deadline` - Point in time after which no further IDs are claimed
+    ///
+    /// # Returns
+    /// * `Vec<u64>` - Monotonically increasing IDs, possibly fewer than `n`
+    pub fn reserve_until(&self, n: usize, deadline: Instant) -> Vec<u64> {
+        let mut ids = Vec::with_capacity(n.min(self.config.max_sequence_id() as usize + 1));
+
+        while ids.len() < n && Instant::now() < deadline {
+            ids.push(self.generate());
+        }
+
+        ids
+    }
+
+    /// Generate a block of `count` SnowIDs with a single clock read
+    ///
+    /// The block starts at the millisecond after the last one used and fills
+    /// every sequence number before moving to the next millisecond, so it is
+    /// much faster than repeated `generate` calls for backfills. For large
+    /// counts the timestamps run ahead of the wall clock, by one millisecond
+    /// per `max_sequence_id() + 1` IDs, and later calls to `generate` wait
+    /// until the clock has caught up.
+    ///
+    /// # Arguments
+    /// * `count` - Number of IDs to generate
+    ///
+    /// # Returns
+    /// * `Result<Vec<u64>, SnowIDError>` - Strictly increasing IDs or error if the
+    ///   block would run past the timestamp range
+    pub fn generate_bulk(&self, count: usize) -> Result<Vec<u64>, SnowIDError> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let slots = self.sequence_limit as u64 + 1;
+        let extra_ms = (count as u64 - 1) / slots;
+
+        let last_sequence = ((count as u64 - 1) % slots) as u16;
+        let mut node_id = self.node_id();
+        let mut current = self.state.load(Ordering::Acquire);
+        let start = loop {
+            let (last_ts, _) = Self::unpack_state(current);
+            let start = self.get_time_since_epoch().max(last_ts + 1);
+            if start + extra_ms > self.config.timestamp_mask() {
+                return Err(SnowIDError::ComponentOutOfRange {
+                    component: "timestamp",
+                    value: start + extra_ms,
+                    max: self.config.timestamp_mask(),
+                });
+            }
+            match self.state.compare_exchange(
+                current,
+                Self::pack_state(start + extra_ms, last_sequence),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) if self.node_id() != node_id => {
+                    // The node changed mid-claim; claim a new block under it
+                    node_id = self.node_id();
+                    current = self.state.load(Ordering::Acquire);
+                }
+                Ok(_) => break start,
+                Err(actual) => current = actual,
+            }
+        };
+
+        Ok((0..count as u64)
+            .map(|i| self.create_snowid(start + i / slots, node_id, (i % slots) as u16))
+            .collect())
+    }
+
+    /// Get the node ID currently embedded in generated IDs
+    #[inline]
+    pub fn node_id(&self) -> u16 {
+        self.node_id.load(Ordering::Acquire)
+    }
+
+    /// Get the spare node IDs this generator falls back to under sustained load
+    ///
+    /// # Returns
+    /// * `&[u16]` - Spare node IDs in the order they are used, empty if none
+    pub fn spare_nodes(&self) -> &[u16] {
+        self.spares.as_ref().map_or(&[], |spares| spares.nodes())
+    }
+
+    /// Describe this generator's node ID and layout for publishing
+    ///
+    /// # Returns
+    /// * `GeneratorDescriptor` - Descriptor accepted by `SnowID::from_descriptor`
+    pub fn descriptor(&self) -> GeneratorDescriptor {
+        GeneratorDescriptor {
+            node_id: self.node_id(),
+            config_descriptor: ConfigDescriptor::from(self.config),
+        }
+    }
+
+    /// Serialize this generator's state into a compact checkpoint
+    ///
+    /// The 22-byte big-endian format is a format version byte, the node ID,
+    /// the layout as in `ConfigDescriptor` (timestamp, node and sequence bits,
+    /// then the epoch) and the last used timestamp. The sequence policy, reset
+    /// base and spare nodes are not included.
+    ///
+    /// # Returns
+    /// * `Vec<u8>` - Checkpoint bytes accepted by `SnowID::restore`
+    pub fn checkpoint(&self) -> Vec<u8> {
+        let descriptor = self.descriptor();
+        let layout = descriptor.config_descriptor;
+
+        let mut bytes = Vec::with_capacity(Self::STATE_LEN);
+        bytes.push(Self::STATE_FORMAT_VERSION);
+        bytes.extend_from_slice(&descriptor.node_id.to_be_bytes());
+        bytes.extend_from_slice(&[
+            layout.timestamp_bits,
+            layout.node_bits,
+            layout.sequence_bits,
+        ]);
+        bytes.extend_from_slice(&layout.epoch.to_be_bytes());
+        bytes.extend_from_slice(&self.load_state().0.to_be_bytes());
+        bytes
+    }
+
+    /// Check whether a SnowID carries this generator's node ID
+    ///
+    /// Helps a service notice IDs produced by another node, e.g. after a
+    /// routing bug. Only the node field is compared.
+    ///
+    /// # Arguments
+    /// * `id` - SnowID to check
+    ///
+    /// # Returns
+    /// * `bool` - `true` if the node field matches the current node ID
+    #[inline]
+    pub fn is_mine(&self, id: u64) -> bool {
+        self.extract.node(id) == self.node_id()
+    }
+
+    /// Change the node ID used for subsequently generated IDs
+    ///
+    /// Timestamp and sequence state are preserved. The current millisecond is
+    /// marked as exhausted first, so the next ID moves to a new millisecond and
+    /// stays greater than every ID issued under the previous node ID. Claims in
+    /// flight on other threads keep the node ID they started with, or claim
+    /// again under the new one, so no ID pairs the new node ID with a
+    /// millisecond from before the switch.
+    ///
+    /// # Arguments
+    /// * `node_id` - New node ID to use in generated IDs
+    ///
+    /// # Returns
+    /// * `Result<(), SnowIDError>` - Ok or error if node_id is invalid
+    pub fn set_node_id(&self, node_id: u16) -> Result<(), SnowIDError> {
+        if node_id > self.config.max_node_id() {
+            return Err(SnowIDError::InvalidNodeId {
+                node_id,
+                max: self.config.max_node_id(),
+            });
+        }
+
+        let _ = self
+            .state
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                let (last_ts, _) = Self::unpack_state(current);
+                let tag_bits = Self::state_tag_bits(current);
+                Some(Self::pack_tagged_state(
+                    last_ts,
+                    self.sequence_limit,
+                    tag_bits,
+                ))
+            });
+        self.node_id.store(node_id, Ordering::Release);
+        Ok(())
+    }
+
+    /// Generate a new SnowID for a caller-supplied time instead of the system clock
+    ///
+    /// Meant for deterministic replay and tests. Calls with the same time share
+    /// the sequence counter; a time earlier than the last one used by this
+    /// generator is rejected rather than adjusted.
+    ///
+    /// Like `generate`, this never returns 0: at the epoch itself on a node 0
+    /// generator the first sequence value is skipped.
+    ///
+    /// # Arguments
+    /// * `unix_millis` - Time of the ID in milliseconds since Unix epoch
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - New SnowID value or error if the time is before the
+    ///   epoch, beyond the timestamp range, goes backwards, or its sequence is exhausted
+    pub fn generate_with_time(&self, unix_millis: u64) -> Result<u64, SnowIDError> {
+        let epoch = self.config.epoch();
+        let timestamp = unix_millis
+            .checked_sub(epoch)
+            .ok_or(SnowIDError::TimeBeforeEpoch {
+                time: unix_millis,
+                epoch,
+            })?;
+        if timestamp > self.config.timestamp_mask() {
+            return Err(SnowIDError::ComponentOutOfRange {
+                component: "timestamp",
+                value: timestamp,
+                max: self.config.timestamp_mask(),
+            });
+        }
+
+        let mut node_id = self.node_id();
+        let mut current = self.state.load(Ordering::Acquire);
+        loop {
+            let (last_ts, last_sequence) = Self::unpack_state(current);
+            let sequence = if timestamp > last_ts {
+                // Keep 0 free as an "unset" sentinel by skipping to the next sequence
+                let first = u16::from(self.create_snowid(timestamp, node_id, 0) == 0);
+                if first > self.sequence_limit {
+                    return Err(SnowIDError::SequenceExhausted { timestamp });
+                }
+                first
+            } else if timestamp == last_ts {
+                // A millisecond claimed by tagged IDs has no untagged sequence left
+                if last_sequence >= self.sequence_limit || Self::state_tag_bits(current) != 0 {
+                    return Err(SnowIDError::SequenceExhausted { timestamp });
+                }
+                last_sequence + 1
+            } else {
+                return Err(SnowIDError::ClockMovedBackwards {
+                    delta: (last_ts - timestamp) as i64,
+                });
+            };
+
+            match self.state.compare_exchange(
+                current,
+                Self::pack_state(timestamp, sequence),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) if self.node_id() != node_id => {
+                    // The node changed mid-claim; claim again under it
+                    node_id = self.node_id();
+                    current = self.state.load(Ordering::Acquire);
+                }
+                Ok(_) => return Ok(self.create_snowid(timestamp, node_id, sequence)),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Generate a new SnowID created no earlier than the given time
+    ///
+    /// Useful when an operation must not appear to precede a known event. If
+    /// the time lies ahead of the local clock, the ID uses that millisecond.
+    /// The internal timestamp then has to move up to it as well, since issuing
+    /// the same millisecond again once the clock catches up could repeat IDs;
+    /// later `generate` calls therefore also stay at or above it.
+    ///
+    /// # Arguments
+    /// * `min_unix_millis` - Earliest allowed creation time in milliseconds since Unix epoch
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - New SnowID value or error if the time does not
+    ///   fit in the timestamp field
+    pub fn generate_at_least(&self, min_unix_millis: u64) -> Result<u64, SnowIDError> {
+        let min_timestamp = min_unix_millis.saturating_sub(self.config.epoch());
+        if min_timestamp > self.config.timestamp_mask() {
+            return Err(SnowIDError::ComponentOutOfRange {
+                component: "timestamp",
+                value: min_timestamp,
+                max: self.config.timestamp_mask(),
+            });
+        }
+
+        self.raise_timestamp(min_timestamp);
+        Ok(self.generate())
+    }
+
+    /// Generate a new SnowID strictly greater than the given floor
+    ///
+    /// Intended for leader handoff: a new instance can pass the highest ID
+    /// persisted by its predecessor and continue without reissuing it. If the
+    /// floor lies ahead of the local clock, the internal timestamp is bumped past
+    /// it, and every later `generate` call also stays above the floor.
+    ///
+    /// # Arguments
+    /// * `floor` - ID that the result must exceed
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - New SnowID value or error if no greater ID can exist
+    pub fn generate_above(&self, floor: u64) -> Result<u64, SnowIDError> {
+        let floor_timestamp = self.extract.timestamp(floor);
+        if floor_timestamp >= self.config.timestamp_mask() {
+            return Err(SnowIDError::FloorOutOfRange { floor });
+        }
+
+        // Move to the millisecond after the floor unless we are already past it
+        self.raise_timestamp(floor_timestamp + 1);
+        Ok(self.generate())
+    }
+
+    /// Move the last used timestamp up to `timestamp` with a fresh sequence, if it is lower
+    fn raise_timestamp(&self, timestamp: u64) {
+        let _ = self
+            .state
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                let (last_ts, _) = Self::unpack_state(current);
+                (last_ts < timestamp).then(|| Self::pack_state(timestamp, 0))
+            });
+    }
+
+    /// Claim the next (timestamp, node ID, sequence), waiting on exhaustion
+    fn next_timestamp_and_sequence(&self) -> (u64, u16, u16) {
+        self.claim_timestamp_and_sequence(self.waiting_policy(), self.sequence_limit, 0)
+            .expect("waiting policies never fail")
+    }
+
+    /// Configured sequence policy, with `Error` replaced by a waiting policy
+    fn waiting_policy(&self) -> SequencePolicy {
+        match self.config.sequence_policy() {
+            SequencePolicy::Error => SequencePolicy::RollToNextMs,
+            policy => policy,
+        }
+    }
+
+    /// Generate from the primary node, falling back to a spare when it is exhausted
+    fn generate_with_spares(
+        &self,
+        spares: &spare::SpareNodes,
+        policy: SequencePolicy,
+    ) -> Result<u64, SnowIDError> {
+        match self.claim_timestamp_and_sequence(SequencePolicy::Error, self.sequence_limit, 0) {
+            Ok((timestamp, node_id, sequence)) => {
+                return Ok(self.create_snowid(timestamp, node_id, sequence))
+            }
+            Err(SnowIDError::SequenceExhausted { timestamp }) => {
+                if spares.record_exhaustion(timestamp) {
+                    if let Some((node_id, sequence)) = spares.claim(timestamp) {
+                        return Ok(self.create_snowid_with_node(
+                            timestamp,
+                            node_id,
+                            self.sequence_base + sequence,
+                        ));
+                    }
+                }
+            }
+            Err(error) => return Err(error),
+        }
+
+        let (timestamp, node_id, sequence) =
+            self.claim_timestamp_and_sequence(policy, self.sequence_limit, 0)?;
+        Ok(self.create_snowid(timestamp, node_id, sequence))
+    }
+
+    /// Claim the next (timestamp, node ID, sequence) for this generator
+    ///
+    /// A millisecond is claimed with the tag width of its first ID, 0 for
+    /// untagged IDs. Tagged and untagged counters overlap, so a tagged claim
+    /// with another width fails and an untagged claim moves on to the next
+    /// millisecond.
+    ///
+    /// The node ID is read before the claim and checked again after it. If
+    /// `set_node_id` ran in between, the claimed slot may belong to the
+    /// previous node's last millisecond, so it is dropped and claimed again.
+    fn claim_timestamp_and_sequence(
+        &self,
+        policy: SequencePolicy,
+        max_sequence: u16,
+        tag_bits: u8,
+    ) -> Result<(u64, u16, u16), SnowIDError> {
         let mut timestamp = self.get_time_since_epoch();
-        let mut last_ts = self.last_timestamp.load(Ordering::Acquire);
+        let mut node_id = self.node_id();
+        let mut current = self.state.load(Ordering::Acquire);
+        // Claims are sampled by their starting sequence counter, so skipping
+        // one costs no clock read
+        #[cfg(feature = "latency_stats")]
+        let mut timer = (current % latency::SAMPLE_EVERY == 0).then(latency::LatencyTimer::start);
         let mut backoff = 1;
 
-        loop {
+        let sequence = loop {
+            let (last_ts, last_sequence) = Self::unpack_state(current);
+
             if timestamp > last_ts {
-                // Try to update last_timestamp atomically
-                match self.last_timestamp.compare_exchange(
-                    last_ts,
-                    timestamp,
+                // Start the new millisecond and reset the sequence in one transition
+                match self.state.compare_exchange(
+                    current,
+                    Self::pack_tagged_state(timestamp, 0, tag_bits),
                     Ordering::AcqRel,
                     Ordering::Acquire,
                 ) {
+                    Ok(_) if self.node_id() != node_id => {
+                        node_id = self.node_id();
+                        current = self.state.load(Ordering::Acquire);
+                        continue;
+                    }
                     Ok(_) => {
-                        self.sequence.store(0, Ordering::Release);
-                        break;
+                        // Report how full the millisecond just left behind ended up
+                        #[cfg(feature = "prometheus")]
+                        self.metrics
+                            .utilization
+                            .set((last_sequence as f64 + 1.0) / (max_sequence as f64 + 1.0));
+                        break 0;
                     }
                     Err(actual) => {
-                        last_ts = actual;
+                        current = actual;
                         continue;
                     }
                 }
             } else {
-                // For same timestamp or backwards clock
-                let current_sequence = self.sequence.fetch_add(1, Ordering::AcqRel);
+                #[cfg(feature = "prometheus")]
+                if timestamp < last_ts {
+                    self.metrics.clock_regressions.inc();
+                }
+
+                #[cfg(feature = "tracing")]
+                if timestamp < last_ts {
+                    tracing::trace!(
+                        node_id = self.node_id(),
+                        timestamp,
+                        last_timestamp = last_ts,
+                        "clock moved backwards"
+                    );
+                }
 
-                if current_sequence < self.config.max_sequence_id() {
-                    // We got a valid sequence number
-                    break;
+                let same_width = Self::state_tag_bits(current) == tag_bits;
+                if !same_width && tag_bits != 0 {
+                    return Err(SnowIDError::InvalidLayout {
+                        reason: "tag bits differ from those used in this millisecond",
+                    });
                 }
 
+                // For same timestamp or backwards clock
+                if same_width && last_sequence < max_sequence {
+                    match self.state.compare_exchange(
+                        current,
+                        Self::pack_tagged_state(last_ts, last_sequence + 1, tag_bits),
+                        Ordering::AcqRel,
+                        Ordering::Acquire,
+                    ) {
+                        Ok(_) if self.node_id() != node_id => {
+                            node_id = self.node_id();
+                            current = self.state.load(Ordering::Acquire);
+                            continue;
+                        }
+                        Ok(_) => {
+                            // We got a valid sequence number; never fall behind the last timestamp
+                            timestamp = last_ts;
+                            break last_sequence + 1;
+                        }
+                        Err(actual) => {
+                            current = actual;
+                            continue;
+                        }
+                    }
+                }
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    node_id = self.node_id(),
+                    timestamp = last_ts,
+                    sequence = last_sequence,
+                    "sequence exhausted"
+                );
+
+                #[cfg(feature = "prometheus")]
+                self.metrics.exhausted.inc();
+
+                #[cfg(feature = "latency_stats")]
+                timer
+                    .get_or_insert_with(latency::LatencyTimer::start)
+                    .wait();
+
                 // Sequence exhausted, wait for next millisecond with exponential backoff
                 let wait_from = timestamp.max(last_ts);
-                timestamp = self.wait_next_millis(wait_from, backoff);
+                timestamp = match policy {
+                    SequencePolicy::Error => {
+                        return Err(SnowIDError::SequenceExhausted { timestamp: last_ts })
+                    }
+                    SequencePolicy::BlockUntilNextMs => self.wait_next_millis(wait_from, 0),
+                    SequencePolicy::RollToNextMs => self.wait_next_millis(wait_from, backoff),
+                };
                 backoff = (backoff * 2).min(Self::MAX_BACKOFF_MS);
 
-                // Update last_ts for next iteration
-                last_ts = self.last_timestamp.load(Ordering::Acquire);
+                // Reload the state for the next iteration
+                current = self.state.load(Ordering::Acquire);
             }
+        };
+
+        #[cfg(feature = "latency_stats")]
+        if let Some(timer) = timer {
+            self.latency.finish(timer);
         }
 
-        self.create_snowid(timestamp, self.sequence.load(Ordering::Acquire))
+        #[cfg(feature = "prometheus")]
+        self.metrics.generated.inc();
+
+        Ok((timestamp, node_id, sequence))
+    }
+
+    /// Get percentiles of how long generating an ID has taken on this generator
+    ///
+    /// Every claim that has to wait for the next millisecond after sequence
+    /// exhaustion is timed, so latency spikes show up directly in `p99` and
+    /// `max`. Of the claims that do not wait, one in 64 is timed and counted
+    /// for the 64, keeping clock reads off the fast path.
+    ///
+    /// # Returns
+    /// * `LatencyStats` - Latency summary since the generator was created
+    #[cfg(feature = "latency_stats")]
+    pub fn latency_stats(&self) -> LatencyStats {
+        self.latency.stats()
+    }
+
+    /// Register this generator's metrics with a Prometheus registry
+    ///
+    /// Exports `snowid_ids_generated_total`, `snowid_sequence_exhausted_total`,
+    /// `snowid_clock_regressions_total` and the `snowid_sequence_utilization`
+    /// gauge, labelled with the node ID the generator was created with. Counts
+    /// cover IDs claimed from the system clock; `generate_with_time` is not
+    /// counted.
+    ///
+    /// # Arguments
+    /// * `registry` - Registry to add the collectors to
+    ///
+    /// # Returns
+    /// * `prometheus::Result<()>` - Error if a collector with the same name and
+    ///   node label is already registered
+    #[cfg(feature = "prometheus")]
+    pub fn register_metrics(&self, registry: &prometheus::Registry) -> prometheus::Result<()> {
+        self.metrics.register(registry)
+    }
+
+    /// Measure how far this generator's clock is from a reference time
+    ///
+    /// Useful for spotting nodes with drifting clocks before they start relying
+    /// on clock-regression handling.
+    ///
+    /// # Arguments
+    /// * `reference_unix_millis` - Trusted time (e.g. NTP-synced) in milliseconds since Unix epoch
+    ///
+    /// # Returns
+    /// * `i64` - Signed drift in milliseconds; positive when the local clock is ahead
+    pub fn clock_offset(&self, reference_unix_millis: u64) -> i64 {
+        let local = self.clock_since_epoch() + self.config.epoch();
+        local as i64 - reference_unix_millis as i64
+    }
+
+    /// Get the first timestamp at which this generator starts a fresh sequence
+    ///
+    /// Lets schedulers align batches to millisecond rollovers. When the generator
+    /// has run ahead of the clock, the boundary follows its last timestamp.
+    ///
+    /// # Returns
+    /// * `u64` - Next millisecond boundary, relative to the configured epoch
+    pub fn next_ms_boundary(&self) -> u64 {
+        let (last_ts, _) = self.load_state();
+        self.clock_since_epoch().max(last_ts) + 1
+    }
+
+    /// Estimate how long until the system clock ticks over to the next millisecond
+    ///
+    /// Best-effort: the result reflects the sub-millisecond part of the system
+    /// clock, but IDs only ever carry whole milliseconds.
+    ///
+    /// # Returns
+    /// * `u64` - Microseconds remaining in the current millisecond, in `1..=1000`
+    pub fn micros_until_next_tick(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards");
+
+        1_000 - (now.as_micros() % 1_000) as u64
+    }
+
+    /// Estimate the tick granularity of the system clock
+    ///
+    /// Samples the clock until it has advanced a few times, or for at most
+    /// 200ms, and returns the smallest step observed. On platforms where the
+    /// clock only ticks every ~15ms, each tick gives at most one millisecond's
+    /// worth of sequence numbers, so exhaustion is far more likely than the
+    /// layout suggests. With the `tracing` feature, a resolution coarser than
+    /// 1ms is reported as a warning with the resulting throughput limit.
+    ///
+    /// # Returns
+    /// * `Duration` - Estimated clock resolution, never zero
+    pub fn measure_clock_resolution(&self) -> Duration {
+        const TICKS: usize = 5;
+        const MAX_SAMPLING: Duration = Duration::from_millis(200);
+
+        let started = Instant::now();
+        let mut previous = SystemTime::now();
+        let mut resolution = MAX_SAMPLING;
+        let mut ticks = 0;
+        while ticks < TICKS && started.elapsed() < MAX_SAMPLING {
+            let now = SystemTime::now();
+            if let Ok(step) = now.duration_since(previous) {
+                if !step.is_zero() {
+                    resolution = resolution.min(step);
+                    ticks += 1;
+                }
+            }
+            previous = now;
+        }
+
+        #[cfg(feature = "tracing")]
+        if resolution > Duration::from_millis(1) {
+            tracing::warn!(
+                node_id = self.node_id(),
+                resolution_us = resolution.as_micros() as u64,
+                max_ids_per_second =
+                    (self.sequence_limit as u128 + 1) * 1_000_000 / resolution.as_micros(),
+                "coarse clock resolution"
+            );
+        }
+
+        resolution
+    }
+
+    /// Get when this generator was constructed
+    ///
+    /// Generators restored with `restore` or created by
+    /// `split_sequence_space` report their own construction time.
+    ///
+    /// # Returns
+    /// * `SystemTime` - Wall-clock time of construction
+    #[inline]
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+
+    /// Get how long ago this generator was constructed
+    ///
+    /// # Returns
+    /// * `Duration` - Time since `created_at`, zero if the clock has since moved behind it
+    pub fn age(&self) -> Duration {
+        self.created_at.elapsed().unwrap_or(Duration::ZERO)
+    }
+
+    /// Get a consistent snapshot of the last used timestamp and sequence counter
+    ///
+    /// Both values live in one packed atomic word, so this is a single
+    /// `Ordering::Acquire` load that can never pair the timestamp of one
+    /// claim with the sequence of another. Meant for diagnostics; the state
+    /// may move on as soon as it has been read. The counter excludes the
+    /// sequence reset base, so it starts at 0 in every millisecond.
+    ///
+    /// # Returns
+    /// * `(u64, u16)` - Milliseconds since the epoch and sequence counter of the last claim
+    #[inline]
+    pub fn load_state(&self) -> (u64, u16) {
+        Self::unpack_state(self.state.load(Ordering::Acquire))
+    }
+
+    /// Get how long this generator can run before its timestamp field overflows
+    ///
+    /// Counted from the current clock; with the 42-bit timestamp this is roughly
+    /// 139 years after the configured epoch.
+    ///
+    /// # Returns
+    /// * `Duration` - Time left until the timestamp exceeds its maximum, zero if already past
+    pub fn time_until_overflow(&self) -> Duration {
+        self.time_until_overflow_at(self.clock_since_epoch())
+    }
+
+    /// Predict whether generating `n` IDs now would cross a millisecond boundary
+    ///
+    /// Compares `n` with the sequence numbers left in the current millisecond,
+    /// or with a full millisecond if the clock has already moved on. This is a
+    /// racy snapshot: other threads may claim sequence numbers, or the clock may
+    /// tick, before the batch is generated, so use it only to inform batching.
+    ///
+    /// # Arguments
+    /// * `n` - Number of IDs in the planned batch
+    ///
+    /// # Returns
+    /// * `bool` - `true` if the batch would not fit in a single millisecond
+    pub fn will_span_milliseconds(&self, n: u16) -> bool {
+        self.will_span_milliseconds_at(self.get_time_since_epoch(), n)
+    }
+
+    /// Whether `n` IDs fit in one millisecond when the clock reads `timestamp`
+    fn will_span_milliseconds_at(&self, timestamp: u64, n: u16) -> bool {
+        let (last_ts, last_sequence) = self.load_state();
+        let capacity = if timestamp > last_ts {
+            self.sequence_limit as u32 + 1
+        } else {
+            self.sequence_limit.saturating_sub(last_sequence) as u32
+        };
+
+        n as u32 > capacity
+    }
+
+    /// Time left from `timestamp` until the timestamp field overflows
+    fn time_until_overflow_at(&self, timestamp: u64) -> Duration {
+        Duration::from_millis(self.config.timestamp_mask().saturating_sub(timestamp))
+    }
+
+    /// Map a process ID onto the node IDs available in a configuration
+    fn node_id_for_pid(pid: u32, config: &SnowIDConfig) -> u16 {
+        (pid % config.max_nodes()) as u16
+    }
+
+    /// Mask the low bytes of an IPv4 address into the node ID range
+    #[cfg(feature = "net")]
+    fn node_id_for_ipv4(
+        addr: std::net::Ipv4Addr,
+        octets: u8,
+        config: &SnowIDConfig,
+    ) -> Result<u16, SnowIDError> {
+        if !(1..=4).contains(&octets) {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "octets must be between 1 and 4",
+            });
+        }
+
+        let suffix = (u32::from(addr) as u64) & ((1u64 << (octets * 8)) - 1);
+        Ok((suffix & config.max_node_id() as u64) as u16)
     }
 
     /// Get current time in milliseconds since epoch
+    ///
+    /// # Panics
+    /// Panics if the clock is before the epoch or past the timestamp range,
+    /// rather than letting the timestamp wrap
     fn get_time_since_epoch(&self) -> u64 {
+        let timestamp = self.clock_since_epoch();
+        if timestamp > self.config.timestamp_mask() {
+            panic!(
+                "Current time {} is past the timestamp range of epoch {}",
+                timestamp + self.config.epoch(),
+                self.config.epoch()
+            );
+        }
+
+        timestamp
+    }
+
+    /// Get current time in milliseconds since epoch, even past the timestamp range
+    fn clock_since_epoch(&self) -> u64 {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards");
@@ -149,8 +1392,37 @@ fn wait_next_millis(&self, timestamp: u64, backoff_ms: u64) -> u64 {
     }
 
     #[inline]
-    fn create_snowid(&self, timestamp: u64, sequence: u16) -> u64 {
-        self.create_snowid_with_node(timestamp, self.node_id, sequence)
+    fn pack_state(timestamp: u64, sequence: u16) -> u64 {
+        (timestamp << Self::STATE_COUNTER_BITS) | sequence as u64
+    }
+
+    #[inline]
+    fn pack_tagged_state(timestamp: u64, sequence: u16, tag_bits: u8) -> u64 {
+        ((tag_bits as u64) << Self::STATE_TAG_SHIFT) | Self::pack_state(timestamp, sequence)
+    }
+
+    #[inline]
+    fn unpack_state(state: u64) -> (u64, u16) {
+        (
+            (state >> Self::STATE_COUNTER_BITS) & ((1 << Self::TIMESTAMP_BITS) - 1),
+            state as u16,
+        )
+    }
+
+    #[inline]
+    fn state_tag_bits(state: u64) -> u8 {
+        (state >> Self::STATE_TAG_SHIFT) as u8
+    }
+
+    /// Overwrite the last used timestamp and sequence counter
+    fn store_state(&self, timestamp: u64, sequence: u16) {
+        self.state
+            .store(Self::pack_state(timestamp, sequence), Ordering::Release);
+    }
+
+    #[inline]
+    fn create_snowid(&self, timestamp: u64, node_id: u16, sequence: u16) -> u64 {
+        self.create_snowid_with_node(timestamp, node_id, self.sequence_base + sequence)
     }
 
     #[inline]
@@ -0,0 +1,216 @@
+use std::cell::Cell;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{SequencePolicy, SnowIDConfig, SnowIDError, SnowIDExtractor};
+
+/// Single-threaded SnowID generator without atomic operations
+///
+/// Produces IDs with the same layout as `SnowID` and honors the sequence
+/// policy and sequence reset base of its configuration, but keeps its state in `Cell`s, so it is `Send` but not
+/// `Sync`. Meant to be created once per thread: IDs stay unique across threads
+/// only if every thread's generator has a distinct node ID.
+#[derive(Debug)]
+pub struct LocalSnowID {
+    node_id: u16,
+    pub config: SnowIDConfig,
+    pub extract: SnowIDExtractor,
+    last_timestamp: Cell<u64>,
+    sequence: Cell<u16>,
+}
+
+impl LocalSnowID {
+    /// Create a new single-threaded generator with default configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - Node ID unique to the owning thread
+    ///
+    /// # Returns
+    /// * `Result<LocalSnowID, Error>` - New generator or error if node_id is invalid
+    pub fn new(node_id: u16) -> Result<Self, SnowIDError> {
+        Self::with_config(node_id, SnowIDConfig::default())
+    }
+
+    /// Create a new single-threaded generator with custom configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - Node ID unique to the owning thread
+    /// * `config` - Custom configuration
+    ///
+    /// # Returns
+    /// * `Result<LocalSnowID, Error>` - New generator or error if node_id is invalid
+    ///   or the current time is past the timestamp range
+    pub fn with_config(node_id: u16, config: SnowIDConfig) -> Result<Self, SnowIDError> {
+        if node_id > config.max_node_id() {
+            return Err(SnowIDError::InvalidNodeId {
+                node_id,
+                max: config.max_node_id(),
+            });
+        }
+        config.check_clock_in_range()?;
+
+        Ok(Self {
+            node_id,
+            extract: SnowIDExtractor::new(config),
+            config,
+            last_timestamp: Cell::new(0),
+            sequence: Cell::new(0),
+        })
+    }
+
+    /// Generate a new SnowID
+    ///
+    /// Like `SnowID::generate`, never moves backwards in time and waits for the
+    /// next millisecond when the sequence is exhausted, with
+    /// `SequencePolicy::Error` behaving like `RollToNextMs`.
+    ///
+    /// # Returns
+    /// * `u64` - New SnowID value
+    ///
+    /// # Panics
+    /// Panics if the clock is before the epoch or past the timestamp range
+    pub fn generate(&self) -> u64 {
+        let mut timestamp = self.get_time_since_epoch();
+        let last_ts = self.last_timestamp.get();
+
+        if timestamp > last_ts {
+            self.sequence.set(self.config.sequence_reset_base());
+        } else if self.sequence.get() < self.config.max_sequence_id() {
+            // Same millisecond or clock behind: keep the last timestamp
+            timestamp = last_ts;
+            self.sequence.set(self.sequence.get() + 1);
+        } else {
+            timestamp = self.wait_next_millis(last_ts);
+            self.sequence.set(self.config.sequence_reset_base());
+        }
+        self.last_timestamp.set(timestamp);
+
+        ((timestamp & self.config.timestamp_mask()) << self.config.timestamp_shift())
+            | ((self.node_id as u64) << self.config.node_shift())
+            | self.sequence.get() as u64
+    }
+
+    /// Get the node ID embedded in generated IDs
+    #[inline]
+    pub fn node_id(&self) -> u16 {
+        self.node_id
+    }
+
+    /// Wait until after `timestamp`, sleeping first unless the policy blocks
+    fn wait_next_millis(&self, timestamp: u64) -> u64 {
+        if self.config.sequence_policy() != SequencePolicy::BlockUntilNextMs {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let mut new_timestamp = self.get_time_since_epoch();
+        while new_timestamp <= timestamp {
+            thread::yield_now();
+            new_timestamp = self.get_time_since_epoch();
+        }
+
+        new_timestamp
+    }
+
+    fn get_time_since_epoch(&self) -> u64 {
+        let current_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as u64;
+        let epoch_time = self.config.epoch();
+
+        if current_time <= epoch_time {
+            panic!(
+                "Current time {} is before epoch {}",
+                current_time, epoch_time
+            );
+        }
+
+        let timestamp = current_time - epoch_time;
+        if timestamp > self.config.timestamp_mask() {
+            panic!(
+                "Current time {} is past the timestamp range of epoch {}",
+                current_time, epoch_time
+            );
+        }
+
+        timestamp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monotonic() {
+        let generator = LocalSnowID::new(5).unwrap();
+
+        let ids: Vec<u64> = (0..10_000).map(|_| generator.generate()).collect();
+        assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+        assert!(ids.iter().all(|&id| generator.extract.node(id) == 5));
+    }
+
+    #[test]
+    fn test_sequence_exhaustion() {
+        let generator = LocalSnowID::new(5).unwrap();
+        let timestamp = generator.extract.timestamp(generator.generate()) + 3;
+
+        // Park the generator ahead of the clock with one sequence value left
+        generator.last_timestamp.set(timestamp);
+        generator
+            .sequence
+            .set(generator.config.max_sequence_id() - 1);
+
+        let last = generator.generate();
+        assert_eq!(
+            generator.extract.decompose(last),
+            (timestamp, 5, generator.config.max_sequence_id())
+        );
+
+        let next = generator.generate();
+        assert!(generator.extract.timestamp(next) > timestamp);
+        assert_eq!(generator.extract.sequence(next), 0);
+    }
+
+    #[test]
+    fn test_sequence_reset_base() {
+        let config = SnowIDConfig::builder().sequence_reset_base(10).build();
+        let generator = LocalSnowID::with_config(5, config).unwrap();
+        assert_eq!(generator.extract.sequence(generator.generate()), 10);
+
+        // The millisecond after an exhausted one starts at the base as well
+        let timestamp = generator.extract.timestamp(generator.generate());
+        generator.sequence.set(config.max_sequence_id());
+        let next = generator.generate();
+        assert!(generator.extract.timestamp(next) > timestamp);
+        assert_eq!(generator.extract.sequence(next), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "past the timestamp range")]
+    fn test_timestamp_range() {
+        let config = SnowIDConfig::builder()
+            .epoch(0)
+            .reserved_high_bits(6)
+            .build();
+        assert!(matches!(
+            LocalSnowID::with_config(5, config),
+            Err(SnowIDError::ComponentOutOfRange { .. })
+        ));
+
+        // A generator whose range runs out while it is in use stops instead of wrapping
+        let mut generator = LocalSnowID::new(5).unwrap();
+        generator.config = config;
+        generator.generate();
+    }
+
+    #[test]
+    fn test_invalid_node_id() {
+        assert!(matches!(
+            LocalSnowID::new(1024),
+            Err(SnowIDError::InvalidNodeId { .. })
+        ));
+    }
+}
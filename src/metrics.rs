@@ -0,0 +1,91 @@
+use prometheus::core::{Collector, Desc};
+use prometheus::proto::MetricFamily;
+use prometheus::{Gauge, IntCounter, Opts, Registry};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Prometheus collectors updated from a generator's claim path
+///
+/// Counters are plain atomics shared with the registry. The per-ID counter
+/// skips the prometheus counter entirely until the registry is gathered.
+#[derive(Debug)]
+pub(crate) struct Metrics {
+    pub(crate) generated: PendingCounter,
+    pub(crate) exhausted: IntCounter,
+    pub(crate) clock_regressions: IntCounter,
+    pub(crate) utilization: Gauge,
+}
+
+impl Metrics {
+    pub(crate) fn new(node_id: u16) -> Self {
+        let opts =
+            |name: &str, help: &str| Opts::new(name, help).const_label("node", node_id.to_string());
+
+        Self {
+            generated: PendingCounter::new(
+                IntCounter::with_opts(opts(
+                    "snowid_ids_generated_total",
+                    "Number of SnowIDs generated",
+                ))
+                .expect("metric options are valid"),
+            ),
+            exhausted: IntCounter::with_opts(opts(
+                "snowid_sequence_exhausted_total",
+                "Number of times the sequence ran out within a millisecond",
+            ))
+            .expect("metric options are valid"),
+            clock_regressions: IntCounter::with_opts(opts(
+                "snowid_clock_regressions_total",
+                "Number of times the system clock was behind the last timestamp",
+            ))
+            .expect("metric options are valid"),
+            utilization: Gauge::with_opts(opts(
+                "snowid_sequence_utilization",
+                "Fraction of the sequence range used in the latest completed millisecond",
+            ))
+            .expect("metric options are valid"),
+        }
+    }
+
+    pub(crate) fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.generated.clone()))?;
+        registry.register(Box::new(self.exhausted.clone()))?;
+        registry.register(Box::new(self.clock_regressions.clone()))?;
+        registry.register(Box::new(self.utilization.clone()))
+    }
+}
+
+/// Counter that buffers increments in a plain atomic
+///
+/// Increments are folded into the exported counter whenever the registry is
+/// gathered, so the hot path is a single relaxed add.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingCounter {
+    counter: IntCounter,
+    pending: Arc<AtomicU64>,
+}
+
+impl PendingCounter {
+    fn new(counter: IntCounter) -> Self {
+        Self {
+            counter,
+            pending: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn inc(&self) {
+        self.pending.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Collector for PendingCounter {
+    fn desc(&self) -> Vec<&Desc> {
+        self.counter.desc()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        self.counter.inc_by(self.pending.swap(0, Ordering::Relaxed));
+        self.counter.collect()
+    }
+}
@@ -0,0 +1,118 @@
+//! Process-wide registry of shared generators keyed by node ID
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use crate::{SnowID, SnowIDConfig, SnowIDError};
+
+static REGISTRY: OnceLock<RwLock<HashMap<u16, &'static SnowID>>> = OnceLock::new();
+
+/// Get the shared generator for a node ID, creating it on first use
+///
+/// Every caller asking for the same node ID gets the same generator, so modules
+/// that would otherwise create their own instance for one node cannot issue
+/// colliding IDs. Generators live for the rest of the process.
+///
+/// # Arguments
+/// * `node_id` - Node ID of the shared generator
+/// * `config` - Configuration of the generator; must match the existing one
+///
+/// # Returns
+/// * `Result<&'static SnowID, SnowIDError>` - Shared generator or error if node_id is
+///   invalid or the generator already exists with a different configuration
+pub fn get_or_init(node_id: u16, config: SnowIDConfig) -> Result<&'static SnowID, SnowIDError> {
+    let registry = REGISTRY.get_or_init(Default::default);
+
+    if let Some(&generator) = registry
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&node_id)
+    {
+        return matching(generator, config);
+    }
+
+    let mut generators = registry.write().unwrap_or_else(|e| e.into_inner());
+    if let Some(&generator) = generators.get(&node_id) {
+        return matching(generator, config);
+    }
+
+    let generator: &'static SnowID = Box::leak(Box::new(SnowID::with_config(node_id, config)?));
+    generators.insert(node_id, generator);
+    Ok(generator)
+}
+
+/// Hand out an existing generator only if it was created with `config`
+fn matching(
+    generator: &'static SnowID,
+    config: SnowIDConfig,
+) -> Result<&'static SnowID, SnowIDError> {
+    if generator.config != config {
+        return Err(SnowIDError::InvalidLayout {
+            reason: "node is already registered with a different configuration",
+        });
+    }
+    Ok(generator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::thread;
+
+    #[test]
+    fn test_same_generator_for_node() {
+        let first = get_or_init(900, SnowIDConfig::default()).unwrap();
+        let second = get_or_init(900, SnowIDConfig::default()).unwrap();
+        assert!(std::ptr::eq(first, second));
+
+        let other = get_or_init(901, SnowIDConfig::default()).unwrap();
+        assert!(!std::ptr::eq(first, other));
+    }
+
+    #[test]
+    fn test_config_mismatch() {
+        let first = get_or_init(903, SnowIDConfig::default()).unwrap();
+        let config = SnowIDConfig::builder().node_bits(12).build();
+
+        assert!(matches!(
+            get_or_init(903, config),
+            Err(SnowIDError::InvalidLayout { .. })
+        ));
+        assert!(std::ptr::eq(
+            get_or_init(903, SnowIDConfig::default()).unwrap(),
+            first
+        ));
+    }
+
+    #[test]
+    fn test_invalid_node_id() {
+        assert!(matches!(
+            get_or_init(1024, SnowIDConfig::default()),
+            Err(SnowIDError::InvalidNodeId { .. })
+        ));
+    }
+
+    #[test]
+    fn test_concurrent_access() {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                thread::spawn(|| {
+                    let generator = get_or_init(902, SnowIDConfig::default()).unwrap();
+                    let ids: Vec<u64> = (0..1_000).map(|_| generator.generate()).collect();
+                    (generator as *const SnowID as usize, ids)
+                })
+            })
+            .collect();
+
+        let mut generators = HashSet::new();
+        let mut ids = HashSet::new();
+        for handle in handles {
+            let (generator, generated) = handle.join().unwrap();
+            generators.insert(generator);
+            ids.extend(generated);
+        }
+        assert_eq!(generators.len(), 1);
+        assert_eq!(ids.len(), 8 * 1_000);
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{SequencePolicy, SnowID, SnowIDConfig, SnowIDError, SnowIDExtractor};
+
+/// Round-robin source of per-thread shard hints
+static NEXT_THREAD: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static SHARD_HINT: usize = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Sequence counter padded to its own cache line
+#[derive(Debug)]
+#[repr(align(64))]
+struct Shard(SnowID);
+
+/// Generator that splits the sequence space across independent counters
+///
+/// With `K` shards, the top `log2(K)` sequence bits hold the shard index and
+/// each shard counts through the remaining bits on its own, so threads on
+/// different shards never touch the same atomics. IDs stay unique because the
+/// shards' sequence ranges are disjoint.
+///
+/// The trade-off: each shard only fits `1 / K` of the usual IDs per
+/// millisecond, and IDs are monotonic per shard rather than across the whole
+/// generator. Threads are assigned to shards round-robin on first use.
+#[derive(Debug)]
+pub struct ShardedSnowID {
+    pub config: SnowIDConfig,
+    pub extract: SnowIDExtractor,
+    shards: Box<[Shard]>,
+    shard_bits: u8,
+}
+
+impl ShardedSnowID {
+    /// Create a new sharded generator
+    ///
+    /// As with `SnowID::generate`, `SequencePolicy::Error` behaves like
+    /// `RollToNextMs` since sharded generation cannot fail. The sequence reset
+    /// base is not applied, as each shard needs its full counter range.
+    ///
+    /// # Arguments
+    /// * `node_id` - Node ID to use in generated IDs
+    /// * `config` - Custom configuration
+    /// * `shards` - Number of sequence counters, a power of two
+    ///
+    /// # Returns
+    /// * `Result<ShardedSnowID, SnowIDError>` - New generator or error if node_id is
+    ///   invalid or the shards do not fit in the sequence bits
+    pub fn new(node_id: u16, config: SnowIDConfig, shards: u16) -> Result<Self, SnowIDError> {
+        if !shards.is_power_of_two() {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "shard count must be a power of two",
+            });
+        }
+
+        let shard_bits = shards.trailing_zeros() as u8;
+        if shard_bits >= config.sequence_bits() {
+            return Err(SnowIDError::InvalidLayout {
+                reason: "shards must leave at least one sequence bit",
+            });
+        }
+
+        let policy = match config.sequence_policy() {
+            SequencePolicy::Error => SequencePolicy::RollToNextMs,
+            policy => policy,
+        };
+        let shard_config = SnowIDConfig::builder()
+            .node_bits(config.node_bits())
+            .epoch(config.epoch())
+            .reserved_high_bits(config.reserved_high_bits())
+            .sequence_policy(policy)
+            .build();
+
+        let shards = (0..shards)
+            .map(|_| SnowID::with_config(node_id, shard_config).map(Shard))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            config,
+            extract: SnowIDExtractor::new(config),
+            shards,
+            shard_bits,
+        })
+    }
+
+    /// Generate a new SnowID from the calling thread's shard
+    ///
+    /// # Returns
+    /// * `u64` - New SnowID value
+    pub fn generate(&self) -> u64 {
+        let index = SHARD_HINT.with(|hint| *hint) & (self.shards.len() - 1);
+        self.shards[index]
+            .0
+            .generate_with_high_seq_tag(index as u16, self.shard_bits)
+            .expect("waiting policies never fail")
+    }
+
+    /// Get the number of sequence counters
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Get the node ID of this generator
+    pub fn node_id(&self) -> u16 {
+        self.shards[0].0.node_id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    #[test]
+    fn test_shard_in_sequence_high_bits() {
+        let generator = ShardedSnowID::new(3, SnowIDConfig::default(), 4).unwrap();
+        assert_eq!(generator.shards(), 4);
+        assert_eq!(generator.node_id(), 3);
+
+        let ids: Vec<u64> = (0..100).map(|_| generator.generate()).collect();
+        assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+
+        let (shard, _) = generator.extract.sequence_tag(ids[0], 2);
+        for &id in &ids {
+            assert_eq!(generator.extract.node(id), 3);
+            assert_eq!(generator.extract.sequence_tag(id, 2).0, shard);
+        }
+    }
+
+    #[test]
+    fn test_more_than_256_shards_stay_unique() {
+        let generator = Arc::new(ShardedSnowID::new(1, SnowIDConfig::default(), 512).unwrap());
+        let barrier = Arc::new(Barrier::new(512));
+
+        let handles: Vec<_> = (0..512)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    (0..20).map(|_| generator.generate()).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut ids = HashSet::new();
+        for handle in handles {
+            ids.extend(handle.join().unwrap());
+        }
+        assert_eq!(ids.len(), 512 * 20);
+    }
+
+    #[test]
+    fn test_invalid_shards() {
+        let config = SnowIDConfig::builder().node_bits(16).build();
+
+        for shards in [0, 3, 64] {
+            assert!(matches!(
+                ShardedSnowID::new(1, config, shards),
+                Err(SnowIDError::InvalidLayout { .. })
+            ));
+        }
+        assert!(ShardedSnowID::new(1, config, 32).is_ok());
+        assert!(ShardedSnowID::new(u16::MAX, SnowIDConfig::default(), 2).is_err());
+    }
+
+    #[test]
+    fn test_shards_keep_generation_settings() {
+        let config = SnowIDConfig::builder().reserved_high_bits(2).build();
+        let generator = ShardedSnowID::new(3, config, 4).unwrap();
+
+        for shard in generator.shards.iter() {
+            assert_eq!(shard.0.config.reserved_high_bits(), 2);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Low bits of the claim state count handed-out (spare, sequence) slots. Node
+// and sequence bits add up to 22, so every spare slot of a generator fits.
+const COUNTER_BITS: u32 = 22;
+
+/// Spare node IDs a generator falls back to under sustained exhaustion
+///
+/// Both the pressure tracking and the spare claims live in single atomic
+/// words, so a spare (node, sequence) pair is never handed out twice for the
+/// same millisecond.
+#[derive(Debug)]
+pub(crate) struct SpareNodes {
+    nodes: Box<[u16]>,
+    // Sequence numbers available per spare node and millisecond
+    slots: u64,
+    // Consecutive milliseconds that exhausted the primary node before spares are used
+    threshold: u64,
+    // `last_exhausted_timestamp << 16 | streak`
+    pressure: AtomicU64,
+    // `timestamp << COUNTER_BITS | next_slot`, where a slot is `spare_index * slots + sequence`
+    claims: AtomicU64,
+}
+
+impl SpareNodes {
+    pub(crate) fn new(nodes: &[u16], slots: u64, threshold: u64) -> Self {
+        debug_assert!(nodes.len() as u64 * slots <= 1 << COUNTER_BITS);
+        Self {
+            nodes: nodes.into(),
+            slots,
+            threshold,
+            pressure: AtomicU64::new(0),
+            claims: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn nodes(&self) -> &[u16] {
+        &self.nodes
+    }
+
+    /// Record that the primary node ran out of sequence numbers at `timestamp`
+    ///
+    /// Returns whether exhaustion has now persisted for at least the threshold.
+    pub(crate) fn record_exhaustion(&self, timestamp: u64) -> bool {
+        let mut current = self.pressure.load(Ordering::Acquire);
+        loop {
+            let (last, streak) = (current >> 16, current & 0xFFFF);
+            if timestamp <= last {
+                return streak >= self.threshold;
+            }
+            let streak = if timestamp == last + 1 {
+                (streak + 1).min(0xFFFF)
+            } else {
+                1
+            };
+
+            match self.pressure.compare_exchange_weak(
+                current,
+                timestamp << 16 | streak,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return streak >= self.threshold,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Claim a (node, sequence) pair on a spare node for `timestamp`
+    ///
+    /// Returns `None` once every spare is exhausted for that millisecond, or
+    /// if claims have already moved on to a later one.
+    pub(crate) fn claim(&self, timestamp: u64) -> Option<(u16, u16)> {
+        let mut current = self.claims.load(Ordering::Acquire);
+        loop {
+            let claimed = current >> COUNTER_BITS;
+            let slot = match timestamp.cmp(&claimed) {
+                std::cmp::Ordering::Greater => 0,
+                std::cmp::Ordering::Equal => current & ((1 << COUNTER_BITS) - 1),
+                std::cmp::Ordering::Less => return None,
+            };
+            if slot >= self.nodes.len() as u64 * self.slots {
+                return None;
+            }
+
+            match self.claims.compare_exchange_weak(
+                current,
+                timestamp << COUNTER_BITS | (slot + 1),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    let node = self.nodes[(slot / self.slots) as usize];
+                    return Some((node, (slot % self.slots) as u16));
+                }
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pressure_streak() {
+        let spares = SpareNodes::new(&[9], 4, 3);
+
+        assert!(!spares.record_exhaustion(100));
+        assert!(!spares.record_exhaustion(100));
+        assert!(!spares.record_exhaustion(101));
+        assert!(spares.record_exhaustion(102));
+        assert!(spares.record_exhaustion(103));
+        assert!(spares.record_exhaustion(101));
+
+        // A millisecond without exhaustion resets the streak
+        assert!(!spares.record_exhaustion(105));
+    }
+
+    #[test]
+    fn test_claims_walk_spares_in_order() {
+        let spares = SpareNodes::new(&[9, 12], 2, 1);
+
+        let claims: Vec<_> = (0..5).map(|_| spares.claim(50)).collect();
+        assert_eq!(
+            claims,
+            [
+                Some((9, 0)),
+                Some((9, 1)),
+                Some((12, 0)),
+                Some((12, 1)),
+                None
+            ]
+        );
+
+        assert_eq!(spares.claim(51), Some((9, 0)));
+        assert_eq!(spares.claim(50), None);
+    }
+}
@@ -0,0 +1,107 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::{SequencePolicy, SnowID, SnowIDError};
+
+impl SnowID {
+    /// Turn the generator into an endless asynchronous stream of SnowIDs
+    ///
+    /// Each poll yields at most one ID, so consumers apply backpressure simply
+    /// by polling more slowly. When the sequence is exhausted the stream yields
+    /// to the executor until the next millisecond instead of blocking the thread.
+    ///
+    /// There is no timer to park on, so waiting spins: the stream wakes itself
+    /// right away and keeps returning `Pending`, occupying its executor thread
+    /// between other tasks for up to 1 ms per exhaustion. Those re-polls only
+    /// check the clock, so each exhaustion is counted and traced once.
+    ///
+    /// # Returns
+    /// * `impl Stream<Item = u64>` - Stream of new SnowID values
+    pub fn into_stream(self) -> impl Stream<Item = u64> {
+        SnowIDStream {
+            generator: self,
+            exhausted_at: None,
+        }
+    }
+}
+
+/// Stream returned by `SnowID::into_stream`
+struct SnowIDStream {
+    generator: SnowID,
+    // Timestamp whose sequence ran out, while waiting for the clock to pass it
+    exhausted_at: Option<u64>,
+}
+
+impl Stream for SnowIDStream {
+    type Item = u64;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u64>> {
+        let stream = self.get_mut();
+        let generator = &stream.generator;
+
+        if let Some(timestamp) = stream.exhausted_at {
+            if generator.get_time_since_epoch() <= timestamp {
+                // Still in the exhausted millisecond, retry without claiming again
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            stream.exhausted_at = None;
+        }
+
+        match generator.claim_timestamp_and_sequence(
+            SequencePolicy::Error,
+            generator.sequence_limit,
+            0,
+        ) {
+            Ok((timestamp, node_id, sequence)) => {
+                Poll::Ready(Some(generator.create_snowid(timestamp, node_id, sequence)))
+            }
+            Err(SnowIDError::SequenceExhausted { timestamp }) => {
+                // Ask to be polled again once other tasks had a turn
+                stream.exhausted_at = Some(timestamp);
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Err(error) => unreachable!("claiming a sequence only fails on exhaustion: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::StreamExt;
+
+    #[test]
+    fn test_stream_drains_past_exhaustion() {
+        let generator = SnowID::new(1).unwrap();
+        let max_sequence = generator.config.max_sequence_id() as usize;
+
+        let ids: Vec<u64> = block_on(generator.into_stream().take(3 * max_sequence).collect());
+        assert_eq!(ids.len(), 3 * max_sequence);
+        assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+    }
+
+    #[test]
+    fn test_retries_skip_claiming() {
+        let generator = SnowID::new(1).unwrap();
+        let timestamp = generator.extract.timestamp(generator.generate()) + 50;
+        generator.store_state(timestamp, generator.config.max_sequence_id());
+
+        let mut stream = SnowIDStream {
+            generator,
+            exhausted_at: None,
+        };
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        for _ in 0..10 {
+            assert!(Pin::new(&mut stream).poll_next(&mut cx).is_pending());
+        }
+        assert_eq!(stream.exhausted_at, Some(timestamp));
+
+        #[cfg(feature = "prometheus")]
+        assert_eq!(stream.generator.metrics.exhausted.get(), 1);
+    }
+}
@@ -61,6 +61,30 @@ fn test_component_boundaries() {
         assert!(sequence <= 1023);
     }
 
+    #[test]
+    fn test_wide_node_field_boundaries() {
+        for node_bits in 14..=16 {
+            let config = SnowIDConfig::builder().node_bits(node_bits).build();
+            let max_node = ((1u32 << node_bits) - 1) as u16;
+            assert_eq!(config.max_node_id(), max_node);
+
+            let generator = SnowID::with_config(max_node, config).unwrap();
+            let (_, node, sequence) = generator.extract.decompose(generator.generate());
+            assert_eq!(node, max_node);
+            assert!(sequence <= config.max_sequence_id());
+
+            if node_bits < 16 {
+                assert_eq!(
+                    SnowID::with_config(max_node + 1, config).unwrap_err(),
+                    SnowIDError::InvalidNodeId {
+                        node_id: max_node + 1,
+                        max: max_node
+                    }
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_zero_node_id() {
         let generator = SnowID::new(0).unwrap();
@@ -56,6 +56,34 @@ fn test_concurrent_generation() {
         }
     }
 
+    #[test]
+    fn test_millisecond_boundary_uniqueness() {
+        // 64 sequence values per millisecond keep threads racing over rollovers
+        let config = SnowIDConfig::builder().node_bits(16).build();
+        let generator = Arc::new(SnowID::with_config(1, config).unwrap());
+        let num_threads = 4;
+        let ids_per_thread = 5_000;
+
+        let handles: Vec<_> = (0..num_threads)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || {
+                    (0..ids_per_thread)
+                        .map(|_| generator.generate())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut all_ids = HashSet::new();
+        for handle in handles {
+            let ids = handle.join().unwrap();
+            assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+            all_ids.extend(ids);
+        }
+        assert_eq!(all_ids.len(), num_threads * ids_per_thread);
+    }
+
     #[test]
     fn test_rapid_generation() {
         let generator = SnowID::new(1).unwrap();
@@ -93,4 +121,71 @@ fn test_timestamp_monotonicity() {
             thread::sleep(Duration::from_millis(1));
         }
     }
+
+    #[test]
+    fn test_atomic_high_water_mark() {
+        let generator = Arc::new(SnowID::new(1).unwrap());
+        let latest = Arc::new(AtomicSnowID::default());
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                let latest = Arc::clone(&latest);
+                thread::spawn(move || {
+                    let ids: Vec<u64> = (0..1_000).map(|_| generator.generate()).collect();
+                    for &id in ids.iter().rev() {
+                        latest.fetch_max(id);
+                    }
+                    ids.into_iter().max().unwrap()
+                })
+            })
+            .collect();
+
+        let max = handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .max()
+            .unwrap();
+        assert_eq!(latest.load(), max);
+    }
+
+    #[test]
+    fn test_sharded_uniqueness() {
+        let generator = Arc::new(ShardedSnowID::new(1, SnowIDConfig::default(), 4).unwrap());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || (0..1_000).map(|_| generator.generate()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut all_ids = HashSet::new();
+        for handle in handles {
+            let ids = handle.join().unwrap();
+            assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+            all_ids.extend(ids);
+        }
+        assert_eq!(all_ids.len(), 4 * 1_000);
+    }
+
+    #[test]
+    fn test_split_generators_uniqueness() {
+        let (lower, upper) = SnowID::new(1).unwrap().split_sequence_space();
+
+        let handles: Vec<_> = [lower, upper]
+            .into_iter()
+            .map(|generator| {
+                thread::spawn(move || (0..2_000).map(|_| generator.generate()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut all_ids = HashSet::new();
+        for handle in handles {
+            let ids = handle.join().unwrap();
+            assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+            all_ids.extend(ids);
+        }
+        assert_eq!(all_ids.len(), 2 * 2_000);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::collections::HashSet;
 
 #[test]
 fn test_clock_backwards() {
@@ -6,7 +7,7 @@ fn test_clock_backwards() {
     let snowid1 = generator.generate();
 
     // Simulate clock moving backwards by saving current timestamp
-    let original_timestamp = generator.last_timestamp.load(Ordering::SeqCst);
+    let original_timestamp = generator.load_state().0;
 
     // Generate another ID - it should handle backwards clock gracefully
     let snowid2 = generator.generate();
@@ -31,3 +32,562 @@ fn test_clock_backwards() {
         );
     }
 }
+
+#[test]
+fn test_set_node_id_mid_stream() {
+    let generator = SnowID::new(5).unwrap();
+    let before: Vec<u64> = (0..100).map(|_| generator.generate()).collect();
+    assert!(before.iter().all(|&id| generator.extract.node(id) == 5));
+
+    generator.set_node_id(3).unwrap();
+    assert_eq!(generator.node_id(), 3);
+
+    let after: Vec<u64> = (0..100).map(|_| generator.generate()).collect();
+    assert!(after.iter().all(|&id| generator.extract.node(id) == 3));
+
+    // Switching to a lower node ID must not break monotonicity
+    let all: Vec<u64> = before.into_iter().chain(after).collect();
+    assert!(all.windows(2).all(|pair| pair[1] > pair[0]));
+}
+
+#[test]
+fn test_set_node_id_invalid() {
+    let generator = SnowID::new(5).unwrap();
+    assert_eq!(
+        generator.set_node_id(1024),
+        Err(SnowIDError::InvalidNodeId {
+            node_id: 1024,
+            max: 1023
+        })
+    );
+    assert_eq!(generator.node_id(), 5);
+}
+
+#[test]
+fn test_generate_above_future_floor() {
+    let generator = SnowID::new(1).unwrap();
+    let now = generator.extract.timestamp(generator.generate());
+
+    // Floor from another node, 50ms ahead of the local clock
+    let floor = generator.create_snowid_with_node(now + 50, 1023, 4095);
+    let snowid = generator.generate_above(floor).unwrap();
+    assert!(snowid > floor);
+    assert!(generator.extract.timestamp(snowid) > now + 50);
+
+    // Regular generation keeps going above the floor
+    let next = generator.generate();
+    assert!(next > snowid);
+}
+
+#[test]
+fn test_generate_above_past_floor() {
+    let generator = SnowID::new(1).unwrap();
+    let floor = generator.generate();
+    assert!(generator.generate_above(floor).unwrap() > floor);
+    assert!(generator.generate_above(0).unwrap() > 0);
+}
+
+#[test]
+fn test_generate_above_out_of_range() {
+    let generator = SnowID::new(1).unwrap();
+    assert_eq!(
+        generator.generate_above(u64::MAX),
+        Err(SnowIDError::FloorOutOfRange { floor: u64::MAX })
+    );
+}
+
+#[test]
+fn test_generate_at_least() {
+    let generator = SnowID::new(1).unwrap();
+    let epoch = generator.config.epoch();
+    let now = generator.extract.timestamp(generator.generate());
+
+    // An event 50ms in the future
+    let snowid = generator.generate_at_least(epoch + now + 50).unwrap();
+    assert_eq!(generator.extract.timestamp(snowid), now + 50);
+    assert!(generator.generate() > snowid);
+
+    // A past time changes nothing
+    let current = generator.generate_at_least(epoch).unwrap();
+    assert!(current > snowid);
+    assert!(generator.generate_at_least(0).is_ok());
+
+    assert!(matches!(
+        generator.generate_at_least(epoch + (1 << 42)),
+        Err(SnowIDError::ComponentOutOfRange { .. })
+    ));
+}
+
+#[test]
+fn test_generate_for_shard() {
+    let num_shards = 4;
+
+    for node_id in 0..16 {
+        let generator = SnowID::new(node_id).unwrap();
+        let shard = node_id % num_shards;
+
+        let snowid = generator.generate_for_shard(shard, num_shards).unwrap();
+        assert_eq!(generator.extract.node(snowid) % num_shards, shard);
+
+        let other = (shard + 1) % num_shards;
+        assert_eq!(
+            generator.generate_for_shard(other, num_shards),
+            Err(SnowIDError::ShardMismatch {
+                node_id,
+                shard: other,
+                num_shards
+            })
+        );
+    }
+
+    assert!(SnowID::new(0).unwrap().generate_for_shard(0, 0).is_err());
+}
+
+#[test]
+fn test_is_mine() {
+    let generator = SnowID::new(5).unwrap();
+    let other = SnowID::new(6).unwrap();
+
+    for _ in 0..10 {
+        assert!(generator.is_mine(generator.generate()));
+        assert!(!generator.is_mine(other.generate()));
+        assert!(!other.is_mine(generator.generate()));
+    }
+}
+
+#[test]
+fn test_from_file() {
+    let path = std::env::temp_dir().join(format!("snowid-node-{}", std::process::id()));
+    let config = SnowIDConfig::default();
+
+    std::fs::write(&path, "42\n").unwrap();
+    assert_eq!(SnowID::from_file(&path, config).unwrap().node_id(), 42);
+
+    std::fs::write(&path, "node-7").unwrap();
+    assert_eq!(
+        SnowID::from_file(&path, config).unwrap_err(),
+        SnowIDError::NodeIdParse {
+            value: "node-7".to_string()
+        }
+    );
+
+    std::fs::write(&path, "5000").unwrap();
+    assert!(matches!(
+        SnowID::from_file(&path, config),
+        Err(SnowIDError::InvalidNodeId { node_id: 5000, .. })
+    ));
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(
+        SnowID::from_file(&path, config),
+        Err(SnowIDError::NodeIdFileUnreadable { .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "net")]
+fn test_node_id_for_ipv4() {
+    use std::net::Ipv4Addr;
+
+    let config = SnowIDConfig::default();
+    let addr = Ipv4Addr::new(10, 0, 3, 200);
+
+    assert_eq!(SnowID::node_id_for_ipv4(addr, 1, &config), Ok(200));
+    // 0x03C8 fits in 10 node bits
+    assert_eq!(SnowID::node_id_for_ipv4(addr, 2, &config), Ok(968));
+    // Bits above the node range are masked away, so these two collide
+    assert_eq!(
+        SnowID::node_id_for_ipv4(Ipv4Addr::new(10, 0, 7, 200), 2, &config),
+        Ok(968)
+    );
+    assert!(matches!(
+        SnowID::node_id_for_ipv4(addr, 0, &config),
+        Err(SnowIDError::InvalidLayout { .. })
+    ));
+    assert!(SnowID::node_id_for_ipv4(addr, 4, &config).is_ok());
+}
+
+#[test]
+fn test_from_pid() {
+    let config = SnowIDConfig::builder().node_bits(8).build();
+    let generator = SnowID::from_pid(config);
+    assert_eq!(generator.node_id() as u32, std::process::id() % 256);
+
+    assert_ne!(
+        SnowID::node_id_for_pid(4_100, &config),
+        SnowID::node_id_for_pid(4_101, &config)
+    );
+    assert_eq!(SnowID::node_id_for_pid(300, &config), 44);
+}
+
+#[test]
+fn test_from_allocator() {
+    let allocator = NodeIdAllocator::new(3);
+
+    let generators: Vec<SnowID> = (0..4)
+        .map(|_| SnowID::from_allocator(&allocator, SnowIDConfig::default()).unwrap())
+        .collect();
+    let nodes: HashSet<u16> = generators.iter().map(SnowID::node_id).collect();
+    assert_eq!(nodes.len(), 4);
+    assert!(matches!(
+        SnowID::from_allocator(&allocator, SnowIDConfig::default()),
+        Err(SnowIDError::NodeIdUnavailable { .. })
+    ));
+
+    // An ID that does not fit the layout goes back to the pool
+    let wide = NodeIdAllocator::new(1024);
+    let config = SnowIDConfig::builder().node_bits(6).build();
+    for _ in 0..=1024 {
+        let _ = SnowID::from_allocator(&wide, config);
+    }
+    assert_eq!(wide.allocate(), Some(64));
+}
+
+#[test]
+fn test_checkpoint_round_trip() {
+    let config = SnowIDConfig::builder()
+        .node_bits(12)
+        .epoch(1_640_995_200_000)
+        .reserved_high_bits(2)
+        .build();
+    let generator = SnowID::with_config(77, config).unwrap();
+    let timestamp = generator.extract.timestamp(generator.generate()) + 5;
+    generator.store_state(timestamp, 0);
+    let last = generator.generate();
+
+    let bytes = generator.checkpoint();
+    assert_eq!(bytes.len(), 22);
+    assert_eq!(bytes[0], 1);
+
+    let restored = SnowID::restore(&bytes).unwrap();
+    assert_eq!(restored.node_id(), 77);
+    assert_eq!(restored.descriptor(), generator.descriptor());
+    assert_eq!(restored.checkpoint(), bytes);
+
+    // Resumes past the checkpointed millisecond
+    let next = restored.generate();
+    assert!(next > last);
+    assert!(restored.extract.timestamp(next) > timestamp);
+}
+
+#[test]
+fn test_restore_rejects_malformed() {
+    let generator = SnowID::new(3).unwrap();
+    generator.generate();
+    let bytes = generator.checkpoint();
+
+    assert_eq!(
+        SnowID::restore(&bytes[..21]).unwrap_err(),
+        SnowIDError::InvalidLength {
+            expected: 22,
+            actual: 21
+        }
+    );
+    assert!(SnowID::restore(&[]).is_err());
+
+    let mut future_version = bytes.clone();
+    future_version[0] = 2;
+    assert!(matches!(
+        SnowID::restore(&future_version),
+        Err(SnowIDError::InvalidLayout { .. })
+    ));
+
+    let mut bad_layout = bytes.clone();
+    bad_layout[4] = 5;
+    assert!(SnowID::restore(&bad_layout).is_err());
+
+    let mut bad_node = bytes.clone();
+    bad_node[1..3].copy_from_slice(&1024u16.to_be_bytes());
+    assert!(matches!(
+        SnowID::restore(&bad_node),
+        Err(SnowIDError::InvalidNodeId { .. })
+    ));
+
+    let mut bad_timestamp = bytes;
+    bad_timestamp[14..].copy_from_slice(&(1u64 << 42).to_be_bytes());
+    assert!(matches!(
+        SnowID::restore(&bad_timestamp),
+        Err(SnowIDError::ComponentOutOfRange { .. })
+    ));
+}
+
+#[test]
+fn test_restore_corrupt_bytes() {
+    let generator = SnowID::new(3).unwrap();
+    generator.generate();
+    let bytes = generator.checkpoint();
+
+    let mut overflowing_layout = bytes.clone();
+    overflowing_layout[4] = 16;
+    overflowing_layout[5] = 250;
+    assert!(matches!(
+        SnowID::restore(&overflowing_layout),
+        Err(SnowIDError::InvalidLayout { .. })
+    ));
+
+    // Any single corrupted byte is either rejected or restores a usable generator
+    for at in 0..bytes.len() {
+        for value in 0..=u8::MAX {
+            let mut corrupt = bytes.clone();
+            corrupt[at] = value;
+            if let Ok(restored) = SnowID::restore(&corrupt) {
+                assert_eq!(restored.checkpoint()[..14], corrupt[..14]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_next_ms_boundary() {
+    let generator = SnowID::new(1).unwrap();
+    let timestamp = generator.extract.timestamp(generator.generate());
+    assert!(generator.next_ms_boundary() > timestamp);
+
+    // A generator parked ahead of the clock rolls over after its own timestamp
+    generator.store_state(timestamp + 1_000, 0);
+    assert_eq!(generator.next_ms_boundary(), timestamp + 1_001);
+
+    let micros = generator.micros_until_next_tick();
+    assert!((1..=1_000).contains(&micros));
+}
+
+#[test]
+fn test_created_at_and_age() {
+    let before = SystemTime::now();
+    let generator = SnowID::new(1).unwrap();
+    let after = SystemTime::now();
+
+    assert!(generator.created_at() >= before && generator.created_at() <= after);
+    assert!(generator.age() < Duration::from_secs(1));
+    assert!(generator.created_at() > generator.config.epoch_systemtime());
+
+    thread::sleep(Duration::from_millis(5));
+    assert!(generator.age() >= Duration::from_millis(5));
+}
+
+#[test]
+fn test_time_until_overflow() {
+    let generator = SnowID::new(1).unwrap();
+    let max_timestamp = (1u64 << 42) - 1;
+
+    // Roughly 139 years minus the time elapsed since the 2024 epoch
+    let remaining = generator.time_until_overflow();
+    let elapsed = generator.extract.timestamp(generator.generate());
+    assert!(remaining.as_millis() as u64 >= max_timestamp - elapsed);
+    assert!(remaining > Duration::from_secs(100 * 365 * 86_400));
+
+    assert_eq!(
+        generator.time_until_overflow_at(max_timestamp - 5),
+        Duration::from_millis(5)
+    );
+    assert_eq!(
+        generator.time_until_overflow_at(max_timestamp),
+        Duration::ZERO
+    );
+    assert_eq!(
+        generator.time_until_overflow_at(max_timestamp + 1),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn test_measure_clock_resolution() {
+    let generator = SnowID::new(1).unwrap();
+
+    let resolution = generator.measure_clock_resolution();
+    assert!(resolution > Duration::ZERO);
+    assert!(resolution <= Duration::from_millis(200));
+}
+
+#[test]
+fn test_will_span_milliseconds() {
+    let generator = SnowID::new(1).unwrap();
+    let max_sequence = generator.config.max_sequence_id();
+    let timestamp = generator.extract.timestamp(generator.generate());
+
+    // A fresh millisecond holds the whole sequence range
+    assert!(!generator.will_span_milliseconds_at(timestamp + 1, max_sequence + 1));
+    assert!(generator.will_span_milliseconds_at(timestamp + 1, max_sequence + 2));
+
+    // Near-full sequence with two values left in the current millisecond
+    generator.store_state(timestamp + 3, max_sequence - 2);
+    assert!(!generator.will_span_milliseconds(2));
+    assert!(generator.will_span_milliseconds(3));
+
+    generator.generate();
+    generator.generate();
+    assert!(!generator.will_span_milliseconds(0));
+    assert!(generator.will_span_milliseconds(1));
+}
+
+#[test]
+fn test_clock_offset() {
+    let generator = SnowID::new(1).unwrap();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    assert!(generator.clock_offset(now).abs() < 1_000);
+
+    // Reference ahead of us means our clock is behind
+    let behind = generator.clock_offset(now + 60_000);
+    assert!((-61_000..=-59_000).contains(&behind));
+
+    let ahead = generator.clock_offset(now - 60_000);
+    assert!((59_000..=61_000).contains(&ahead));
+}
+
+#[test]
+fn test_generate_with_time_equal_times() {
+    let generator = SnowID::new(3).unwrap();
+    let time = generator.config.epoch() + 1_000_000;
+
+    let ids: Vec<u64> = (0..5)
+        .map(|_| generator.generate_with_time(time).unwrap())
+        .collect();
+
+    for (sequence, &id) in ids.iter().enumerate() {
+        assert_eq!(
+            generator.extract.decompose(id),
+            (1_000_000, 3, sequence as u16)
+        );
+    }
+
+    let (ts, _, seq) = generator
+        .extract
+        .decompose(generator.generate_with_time(time + 1).unwrap());
+    assert_eq!((ts, seq), (1_000_001, 0));
+}
+
+#[test]
+fn test_generate_with_time_decreasing_time() {
+    let generator = SnowID::new(3).unwrap();
+    let time = generator.config.epoch() + 1_000_000;
+    generator.generate_with_time(time).unwrap();
+
+    assert_eq!(
+        generator.generate_with_time(time - 250),
+        Err(SnowIDError::ClockMovedBackwards { delta: 250 })
+    );
+    assert_eq!(
+        generator.generate_with_time(1),
+        Err(SnowIDError::TimeBeforeEpoch {
+            time: 1,
+            epoch: generator.config.epoch()
+        })
+    );
+}
+
+#[test]
+fn test_generate_with_time_exhaustion() {
+    let config = SnowIDConfig::builder().node_bits(16).build();
+    let generator = SnowID::with_config(3, config).unwrap();
+    let time = config.epoch() + 42;
+
+    for _ in 0..=config.max_sequence_id() {
+        generator.generate_with_time(time).unwrap();
+    }
+    assert_eq!(
+        generator.generate_with_time(time),
+        Err(SnowIDError::SequenceExhausted { timestamp: 42 })
+    );
+}
+
+#[test]
+fn test_load_state_after_known_generates() {
+    let generator = SnowID::new(3).unwrap();
+    assert_eq!(generator.load_state(), (0, 0));
+
+    let time = generator.config.epoch() + 1_000_000;
+    for _ in 0..5 {
+        generator.generate_with_time(time).unwrap();
+    }
+    assert_eq!(generator.load_state(), (1_000_000, 4));
+
+    generator.generate_with_time(time + 7).unwrap();
+    assert_eq!(generator.load_state(), (1_000_007, 0));
+
+    // Clock-based generation leaves the components of its last ID behind
+    let id = generator.generate();
+    let (timestamp, _, sequence) = generator.extract.decompose(id);
+    assert_eq!(generator.load_state(), (timestamp, sequence));
+}
+
+#[test]
+fn test_reserved_bits_past_range() {
+    // 36 timestamp bits only last about 795 days after the epoch
+    let config = SnowIDConfig::builder()
+        .epoch(0)
+        .reserved_high_bits(6)
+        .build();
+    assert!(matches!(
+        SnowID::with_config(1, config),
+        Err(SnowIDError::ComponentOutOfRange {
+            component: "timestamp",
+            ..
+        })
+    ));
+}
+
+#[test]
+#[should_panic(expected = "past the timestamp range")]
+fn test_generate_past_range_panics() {
+    let mut generator = SnowID::new(1).unwrap();
+    generator.config = SnowIDConfig::builder()
+        .epoch(0)
+        .reserved_high_bits(6)
+        .build();
+    generator.generate();
+}
+
+#[test]
+fn test_generate_with_time_out_of_range() {
+    let generator = SnowID::new(3).unwrap();
+    let max = generator.config.timestamp_mask();
+
+    assert_eq!(
+        generator.generate_with_time(generator.config.epoch() + max + 1),
+        Err(SnowIDError::ComponentOutOfRange {
+            component: "timestamp",
+            value: max + 1,
+            max,
+        })
+    );
+    assert_eq!(generator.load_state(), (0, 0));
+}
+
+#[test]
+fn test_generate_never_returns_zero() {
+    let generator = SnowID::new(0).unwrap();
+    let epoch = generator.config.epoch();
+
+    // Timestamp 0 on node 0 would compose to 0 with the first sequence value
+    let id = generator.generate_with_time(epoch).unwrap();
+    assert_ne!(id, 0);
+    assert_eq!(generator.extract.decompose(id), (0, 0, 1));
+
+    let last = generator
+        .generate_with_time(epoch + generator.config.timestamp_mask())
+        .unwrap();
+    assert_ne!(last, 0);
+}
+
+#[test]
+fn test_generate_and_mark() {
+    let generator = SnowID::new(1).unwrap();
+    let hwm = AtomicSnowID::default();
+
+    let mut last = 0;
+    for _ in 0..100 {
+        let id = generator.generate_and_mark(&hwm);
+        assert!(id > last);
+        assert_eq!(hwm.load(), id);
+        last = id;
+    }
+
+    // A mark that is already ahead is left untouched
+    let ahead = AtomicSnowID::new(u64::MAX);
+    generator.generate_and_mark(&ahead);
+    assert_eq!(ahead.load(), u64::MAX);
+}
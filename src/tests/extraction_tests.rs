@@ -22,6 +22,156 @@ fn test_snowid_generation_and_extraction() {
         assert!(generator.extract.timestamp(snowid2) >= generator.extract.timestamp(snowid1));
     }
 
+    #[test]
+    fn test_generate_with_components() {
+        let generator = SnowID::new(42).unwrap();
+
+        for _ in 0..1000 {
+            let (snowid, components) = generator.generate_with_components();
+            assert_eq!(components, generator.extract.decompose(snowid));
+            assert_eq!(components.1, 42);
+        }
+
+        // Generators owning part of the sequence range stay within it
+        let (_, upper) = SnowID::new(42).unwrap().split_sequence_space();
+        let (snowid, components) = upper.generate_with_components();
+        assert_eq!(components, upper.extract.decompose(snowid));
+        assert!(components.2 >= 2048);
+    }
+
+    #[test]
+    fn test_compose_round_trip() {
+        for node_bits in [6, 10, 12, 16] {
+            let config = SnowIDConfig::builder().node_bits(node_bits).build();
+            let generator = SnowID::with_config(config.max_node_id(), config).unwrap();
+
+            for _ in 0..100 {
+                let snowid = generator.generate();
+                let (timestamp, node, sequence) = generator.extract.decompose(snowid);
+                assert_eq!(
+                    generator.extract.compose(timestamp, node, sequence),
+                    Ok(snowid)
+                );
+            }
+
+            let max_timestamp = (1 << SnowID::TIMESTAMP_BITS) - 1;
+            let parts = (
+                max_timestamp,
+                config.max_node_id(),
+                config.max_sequence_id(),
+            );
+            let snowid = generator
+                .extract
+                .compose(parts.0, parts.1, parts.2)
+                .unwrap();
+            assert_eq!(snowid, u64::MAX);
+            assert_eq!(generator.extract.decompose(snowid), parts);
+        }
+    }
+
+    #[test]
+    fn test_reserved_high_bits_stay_zero() {
+        let config = SnowIDConfig::builder().reserved_high_bits(3).build();
+        let generator = SnowID::with_config(1023, config).unwrap();
+        let unreserved = SnowID::new(1023).unwrap();
+
+        for _ in 0..100 {
+            let snowid = generator.generate();
+            assert_eq!(snowid >> 61, 0);
+            assert!(!generator.extract.has_reserved_bits(snowid));
+            assert_eq!(
+                generator.extract.decompose(snowid),
+                unreserved.extract.decompose(snowid)
+            );
+        }
+
+        // The last timestamp fits in 39 bits, later ones are rejected instead of spilling over
+        let time = config.epoch() + (1 << 39) - 1;
+        let last = generator.generate_with_time(time).unwrap();
+        assert_eq!(last >> 61, 0);
+        assert_eq!(generator.extract.timestamp(last), (1 << 39) - 1);
+        assert!(generator.generate_with_time(time + 1).is_err());
+
+        assert!(generator.extract.compose(1 << 39, 0, 0).is_err());
+        assert!(generator.extract.has_reserved_bits(1 << 63));
+        assert!(!unreserved.extract.has_reserved_bits(u64::MAX));
+    }
+
+    #[test]
+    fn test_compose_out_of_range() {
+        let generator = SnowID::new(42).unwrap();
+
+        assert_eq!(
+            generator.extract.compose(1 << 42, 0, 0),
+            Err(SnowIDError::ComponentOutOfRange {
+                component: "timestamp",
+                value: 1 << 42,
+                max: (1 << 42) - 1
+            })
+        );
+        assert!(matches!(
+            generator.extract.compose(0, 1024, 0),
+            Err(SnowIDError::InvalidNodeId { .. })
+        ));
+        assert_eq!(
+            generator.extract.compose(0, 0, 4096),
+            Err(SnowIDError::ComponentOutOfRange {
+                component: "sequence",
+                value: 4096,
+                max: 4095
+            })
+        );
+    }
+
+    #[test]
+    fn test_generate_into() {
+        let generator = SnowID::new(42).unwrap();
+        let mut buf = [0u8; ENCODED_LEN];
+
+        let encoded = generator.generate_into(&mut buf).to_owned();
+        assert_eq!(encoded.len(), ENCODED_LEN);
+
+        let snowid = decode(&encoded).unwrap();
+        assert_eq!(encode(snowid), encoded);
+        assert_eq!(buf, encoded.as_bytes());
+        assert_eq!(generator.extract.node(snowid), 42);
+
+        let next = decode(generator.generate_into(&mut buf)).unwrap();
+        assert!(next > snowid);
+    }
+
+    #[test]
+    fn test_generate_with_string() {
+        let generator = SnowID::new(42).unwrap();
+
+        let (snowid, encoded) = generator.generate_with_string();
+        assert_eq!(encoded, encode(snowid));
+        assert_eq!(decode(&encoded).unwrap(), snowid);
+        assert_eq!(generator.extract.node(snowid), 42);
+
+        let (next, next_encoded) = generator.generate_with_string();
+        assert!(next > snowid);
+        assert!(next_encoded > encoded);
+    }
+
+    #[test]
+    fn test_descending_ids() {
+        let generator = SnowID::new(42).unwrap();
+
+        let first = generator.generate_descending();
+        let second = generator.generate_descending();
+        assert!(second < first, "Newer descending IDs must sort first");
+
+        let (ts1, node1, seq1) = generator.extract.decompose_descending(first);
+        let (ts2, node2, seq2) = generator.extract.decompose_descending(second);
+        assert_eq!((node1, node2), (42, 42));
+        assert!((ts2, seq2) > (ts1, seq1));
+        assert_eq!(
+            generator.extract.decompose_descending(!second),
+            generator.extract.decompose(second)
+        );
+    }
+
     #[test]
     fn test_custom_configuration() {
         let config = SnowIDConfig::builder().node_bits(12).build();
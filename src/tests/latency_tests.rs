@@ -0,0 +1,25 @@
+use crate::*;
+use std::time::Duration;
+
+#[test]
+fn test_exhaustion_raises_max_latency() {
+    let generator = SnowID::new(1).unwrap();
+    for _ in 0..100 {
+        generator.generate();
+    }
+    let baseline = generator.latency_stats();
+    assert!(baseline.p50 <= baseline.p99 && baseline.p99 <= baseline.max);
+    assert!(
+        baseline.max > Duration::ZERO,
+        "claims without waiting are timed"
+    );
+
+    // Park the generator ahead of the clock with its sequence exhausted
+    let timestamp = generator.extract.timestamp(generator.generate()) + 3;
+    generator.store_state(timestamp, generator.config.max_sequence_id());
+    generator.generate();
+
+    let stats = generator.latency_stats();
+    assert!(stats.max >= Duration::from_millis(2));
+    assert!(stats.p50 < Duration::from_millis(1));
+}
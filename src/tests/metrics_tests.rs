@@ -0,0 +1,48 @@
+use crate::*;
+use prometheus::Registry;
+
+fn metric_value(registry: &Registry, name: &str) -> f64 {
+    let family = registry
+        .gather()
+        .into_iter()
+        .find(|family| family.get_name() == name)
+        .unwrap();
+    let metric = &family.get_metric()[0];
+
+    if name.ends_with("_total") {
+        metric.get_counter().get_value()
+    } else {
+        metric.get_gauge().get_value()
+    }
+}
+
+#[test]
+fn test_counters_increment() {
+    let generator = SnowID::new(7).unwrap();
+    let registry = Registry::new();
+    generator.register_metrics(&registry).unwrap();
+
+    for _ in 0..100 {
+        generator.generate();
+    }
+    assert_eq!(metric_value(&registry, "snowid_ids_generated_total"), 100.0);
+    assert!(metric_value(&registry, "snowid_sequence_utilization") > 0.0);
+
+    // Park the generator ahead of the clock with its sequence exhausted
+    let timestamp = generator.extract.timestamp(generator.generate()) + 3;
+    generator.store_state(timestamp, generator.config.max_sequence_id());
+    generator.generate();
+
+    assert_eq!(metric_value(&registry, "snowid_ids_generated_total"), 102.0);
+    assert!(metric_value(&registry, "snowid_sequence_exhausted_total") >= 1.0);
+    assert!(metric_value(&registry, "snowid_clock_regressions_total") >= 1.0);
+}
+
+#[test]
+fn test_register_twice_fails() {
+    let registry = Registry::new();
+    SnowID::new(7).unwrap().register_metrics(&registry).unwrap();
+
+    assert!(SnowID::new(7).unwrap().register_metrics(&registry).is_err());
+    assert!(SnowID::new(8).unwrap().register_metrics(&registry).is_ok());
+}
@@ -1,5 +1,13 @@
+// Boundary checks spell out maximums as `(1 << bits) - 1`
+#[allow(clippy::int_plus_one)]
 mod boundary_tests;
 mod concurrent_tests;
 mod core_tests;
 mod extraction_tests;
+#[cfg(feature = "latency_stats")]
+mod latency_tests;
+#[cfg(feature = "prometheus")]
+mod metrics_tests;
 mod sequence_tests;
+#[cfg(feature = "tracing")]
+mod tracing_tests;
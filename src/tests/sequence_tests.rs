@@ -1,7 +1,7 @@
 use crate::*;
 use std::collections::HashSet;
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[test]
 fn test_sequence_rollover() {
@@ -103,6 +103,324 @@ fn test_sequence_overflow_handling() {
     );
 }
 
+#[test]
+fn test_wide_sequence_reaches_maximum() {
+    for node_bits in [6, 8, 10] {
+        let config = SnowIDConfig::builder().node_bits(node_bits).build();
+        let generator = SnowID::with_config(1, config).unwrap();
+        let max_sequence = config.max_sequence_id();
+
+        // Park the generator a few ms ahead with the sequence near its end
+        let timestamp = generator.extract.timestamp(generator.generate()) + 5;
+        generator.store_state(timestamp, max_sequence - 2);
+
+        let (ts1, _, seq1) = generator.extract.decompose(generator.generate());
+        let (ts2, _, seq2) = generator.extract.decompose(generator.generate());
+        assert_eq!((ts1, seq1), (timestamp, max_sequence - 1));
+        assert_eq!((ts2, seq2), (timestamp, max_sequence));
+
+        // Exhaustion moves to the next millisecond instead of wrapping the sequence
+        let (ts3, _, seq3) = generator.extract.decompose(generator.generate());
+        assert!(ts3 > timestamp);
+        assert_eq!(seq3, 0);
+    }
+}
+
+#[test]
+fn test_reserve_until_deadline() {
+    let generator = SnowID::new(1).unwrap();
+
+    // Far more IDs than a few milliseconds can hold
+    let ids = generator.reserve_until(usize::MAX, Instant::now() + Duration::from_millis(5));
+    assert!(!ids.is_empty());
+    assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+
+    let ids = generator.reserve_until(10, Instant::now() + Duration::from_secs(5));
+    assert_eq!(ids.len(), 10);
+
+    let ids = generator.reserve_until(10, Instant::now());
+    assert!(ids.is_empty());
+}
+
+#[test]
+fn test_generate_bulk() {
+    let generator = SnowID::new(1).unwrap();
+    let slots = generator.config.max_sequence_id() as usize + 1;
+    let before = generator.generate();
+
+    let ids = generator.generate_bulk(10_000).unwrap();
+    assert_eq!(ids.len(), 10_000);
+    assert!(ids[0] > before);
+    assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), ids.len());
+
+    // Each millisecond is filled completely before the next one starts
+    let start = generator.extract.timestamp(ids[0]);
+    assert_eq!(generator.extract.decompose(ids[0]), (start, 1, 0));
+    assert_eq!(generator.extract.decompose(ids[slots]), (start + 1, 1, 0));
+
+    // Regular generation continues above the block
+    assert!(generator.generate() > ids[ids.len() - 1]);
+    assert!(generator.generate_bulk(0).unwrap().is_empty());
+}
+
+#[test]
+fn test_generate_bulk_range() {
+    let generator = SnowID::new(1).unwrap();
+    let slots = generator.config.max_sequence_id() as usize + 1;
+    let max_timestamp = generator.config.timestamp_mask();
+    generator.store_state(max_timestamp - 2, 0);
+
+    // The block would need the two milliseconds after the last one in range
+    assert_eq!(
+        generator.generate_bulk(2 * slots + 1),
+        Err(SnowIDError::ComponentOutOfRange {
+            component: "timestamp",
+            value: max_timestamp + 1,
+            max: max_timestamp
+        })
+    );
+    assert_eq!(generator.load_state(), (max_timestamp - 2, 0));
+
+    let ids = generator.generate_bulk(2 * slots).unwrap();
+    assert_eq!(
+        generator.extract.timestamp(ids[ids.len() - 1]),
+        max_timestamp
+    );
+}
+
+#[test]
+fn test_sequence_reset_base() {
+    let config = SnowIDConfig::builder()
+        .node_bits(16)
+        .sequence_reset_base(10)
+        .build();
+    assert_eq!(config.sequence_reset_base(), 10);
+    let generator = SnowID::with_config(1, config).unwrap();
+
+    // The first ID of a new millisecond starts at the base
+    let timestamp = generator.extract.timestamp(generator.generate()) + 3;
+    let id = generator
+        .generate_with_time(config.epoch() + timestamp)
+        .unwrap();
+    assert_eq!(generator.extract.decompose(id), (timestamp, 1, 10));
+
+    // Only 54 of the 64 sequence values are generated per millisecond
+    for sequence in 11..=63 {
+        let id = generator
+            .generate_with_time(config.epoch() + timestamp)
+            .unwrap();
+        assert_eq!(generator.extract.sequence(id), sequence);
+    }
+    assert_eq!(
+        generator.generate_with_time(config.epoch() + timestamp),
+        Err(SnowIDError::SequenceExhausted { timestamp })
+    );
+
+    let next = generator.generate();
+    assert!(generator.extract.timestamp(next) > timestamp);
+    assert_eq!(generator.extract.sequence(next), 10);
+}
+
+#[test]
+fn test_high_seq_tag() {
+    let generator = SnowID::new(1).unwrap();
+
+    let ids: Vec<u64> = (0..100)
+        .map(|_| generator.generate_with_high_seq_tag(5, 3).unwrap())
+        .collect();
+    assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+    for &id in &ids {
+        let (tag, counter) = generator.extract.sequence_tag(id, 3);
+        assert_eq!(tag, 5);
+        assert_eq!(generator.extract.sequence(id), (5 << 9) | counter);
+    }
+
+    // The counter rolls over within its 9 remaining bits, not the full 12
+    let timestamp = generator.extract.timestamp(generator.generate()) + 3;
+    generator.state.store(
+        SnowID::pack_tagged_state(timestamp, 510, 3),
+        Ordering::Release,
+    );
+
+    let last = generator.generate_with_high_seq_tag(5, 3).unwrap();
+    assert_eq!(generator.extract.timestamp(last), timestamp);
+    assert_eq!(generator.extract.sequence_tag(last, 3), (5, 511));
+
+    let next = generator.generate_with_high_seq_tag(5, 3).unwrap();
+    assert!(generator.extract.timestamp(next) > timestamp);
+    assert_eq!(generator.extract.sequence_tag(next, 3), (5, 0));
+}
+
+#[test]
+fn test_high_seq_tag_mixed_widths() {
+    let generator = SnowID::new(1).unwrap();
+    let timestamp = generator.extract.timestamp(generator.generate()) + 3;
+
+    // A millisecond claimed with 3 tag bits only takes 3-bit tags
+    generator.state.store(
+        SnowID::pack_tagged_state(timestamp, 0, 3),
+        Ordering::Release,
+    );
+    assert!(matches!(
+        generator.generate_with_high_seq_tag(1, 4),
+        Err(SnowIDError::InvalidLayout { .. })
+    ));
+    let tagged = generator.generate_with_high_seq_tag(1, 3).unwrap();
+    assert_eq!(generator.extract.timestamp(tagged), timestamp);
+
+    // Untagged IDs move on to the next millisecond
+    let untagged = generator.generate();
+    assert!(generator.extract.timestamp(untagged) > timestamp);
+
+    // ... and tagged IDs do not join an untagged millisecond
+    assert!(matches!(
+        generator.generate_with_high_seq_tag(1, 3),
+        Err(SnowIDError::InvalidLayout { .. })
+    ));
+}
+
+#[test]
+fn test_high_seq_tag_validation() {
+    let generator = SnowID::new(1).unwrap();
+
+    assert_eq!(
+        generator.generate_with_high_seq_tag(8, 3),
+        Err(SnowIDError::InvalidTag {
+            tag: 8,
+            tag_bits: 3
+        })
+    );
+    assert!(matches!(
+        generator.generate_with_high_seq_tag(0, 12),
+        Err(SnowIDError::InvalidLayout { .. })
+    ));
+    assert!(generator.generate_with_high_seq_tag(255, 8).is_ok());
+
+    // Tags wider than a byte are checked against all of their bits
+    let generator = SnowID::new(1).unwrap();
+    assert!(generator.generate_with_high_seq_tag(300, 9).is_ok());
+    assert_eq!(
+        generator.generate_with_high_seq_tag(512, 9),
+        Err(SnowIDError::InvalidTag {
+            tag: 512,
+            tag_bits: 9
+        })
+    );
+}
+
+#[test]
+fn test_skip() {
+    let generator = SnowID::new(1).unwrap();
+    let max_sequence = generator.config.max_sequence_id();
+
+    let timestamp = generator.extract.timestamp(generator.generate()) + 3;
+    generator.store_state(timestamp, 10);
+
+    // Slots 11 to 15 are reserved
+    generator.skip(5);
+    let (ts, _, seq) = generator.extract.decompose(generator.generate());
+    assert_eq!((ts, seq), (timestamp, 16));
+
+    generator.skip(0);
+    let (_, _, seq) = generator.extract.decompose(generator.generate());
+    assert_eq!(seq, 17);
+
+    // Skipping past the end reserves the rest of the millisecond
+    generator.store_state(timestamp, max_sequence - 2);
+    generator.skip(5);
+    let (ts, _, seq) = generator.extract.decompose(generator.generate());
+    assert!(ts > timestamp);
+    assert_eq!(seq, 0);
+}
+
+#[test]
+fn test_split_sequence_space() {
+    let generator = SnowID::new(9).unwrap();
+    let last = generator.generate();
+    let half = 1 << (generator.config.sequence_bits() - 1);
+
+    let (lower, upper) = generator.split_sequence_space();
+    let lower_ids: Vec<u64> = (0..3 * half).map(|_| lower.generate()).collect();
+    let upper_ids: Vec<u64> = (0..3 * half).map(|_| upper.generate()).collect();
+
+    for (ids, range) in [(&lower_ids, 0..half), (&upper_ids, half..2 * half)] {
+        assert!(ids[0] > last);
+        assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+        for &id in ids.iter() {
+            assert_eq!(lower.extract.node(id), 9);
+            assert!(range.contains(&lower.extract.sequence(id)));
+        }
+    }
+
+    // Halves split again into quarters
+    let (first, second) = upper.split_sequence_space();
+    let seq = first.extract.sequence(first.generate());
+    assert!((half..half + half / 2).contains(&seq));
+    let seq = second.extract.sequence(second.generate());
+    assert!((half + half / 2..2 * half).contains(&seq));
+
+    assert!(matches!(
+        lower.generate_with_high_seq_tag(1, 2),
+        Err(SnowIDError::InvalidLayout { .. })
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Sequence range is too small to split")]
+fn test_split_sequence_space_exhausted() {
+    let mut generator = SnowID::new(1).unwrap();
+    for _ in 0..12 {
+        generator = generator.split_sequence_space().0;
+    }
+    generator.split_sequence_space();
+}
+
+/// Park a generator a few ms ahead of the clock with its sequence exhausted
+fn exhausted_generator(policy: SequencePolicy) -> (SnowID, u64) {
+    let config = SnowIDConfig::builder().sequence_policy(policy).build();
+    let generator = SnowID::with_config(1, config).unwrap();
+
+    let timestamp = generator.extract.timestamp(generator.generate()) + 3;
+    generator.store_state(timestamp, config.max_sequence_id());
+
+    (generator, timestamp)
+}
+
+#[test]
+fn test_policy_roll_to_next_ms() {
+    let (generator, timestamp) = exhausted_generator(SequencePolicy::RollToNextMs);
+    let (ts, _, seq) = generator
+        .extract
+        .decompose(generator.try_generate().unwrap());
+    assert!(ts > timestamp);
+    assert_eq!(seq, 0);
+}
+
+#[test]
+fn test_policy_block_until_next_ms() {
+    let (generator, timestamp) = exhausted_generator(SequencePolicy::BlockUntilNextMs);
+    let (ts, _, seq) = generator
+        .extract
+        .decompose(generator.try_generate().unwrap());
+    assert!(ts > timestamp);
+    assert_eq!(seq, 0);
+}
+
+#[test]
+fn test_policy_error() {
+    let (generator, timestamp) = exhausted_generator(SequencePolicy::Error);
+    assert_eq!(
+        generator.try_generate(),
+        Err(SnowIDError::SequenceExhausted { timestamp })
+    );
+
+    // generate() cannot fail, so it still waits for the next millisecond
+    let (ts, _, _) = generator.extract.decompose(generator.generate());
+    assert!(ts > timestamp);
+    assert!(generator.try_generate().is_ok());
+}
+
 #[test]
 fn test_sequence_restart() {
     let generator = SnowID::new(1).unwrap();
@@ -230,3 +548,91 @@ fn test_10k_unique_ids() {
         NUM_IDS as f64 / unique_timestamps as f64
     );
 }
+
+#[test]
+fn test_leap_second_backward_smear() {
+    let generator = SnowID::new(1).unwrap();
+
+    // A one-second backward step leaves the system clock behind the last timestamp
+    let timestamp = generator.extract.timestamp(generator.generate()) + 1000;
+    generator.store_state(timestamp, 0);
+
+    let ids: Vec<u64> = (0..1000)
+        .map(|_| generator.try_generate().unwrap())
+        .collect();
+    assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+    assert!(ids
+        .iter()
+        .all(|&id| generator.extract.timestamp(id) == timestamp));
+}
+
+#[test]
+fn test_spare_nodes_under_sustained_pressure() {
+    let config = SnowIDConfig::builder()
+        .sequence_policy(SequencePolicy::Error)
+        .build();
+    let generator = SnowID::with_spare_nodes(1, &[9], config).unwrap();
+    let max_sequence = config.max_sequence_id();
+    let timestamp = generator.extract.timestamp(generator.generate()) + 1000;
+
+    let park = |offset: u64, sequence: u16| {
+        generator.store_state(timestamp + offset, sequence);
+    };
+
+    // The first two exhausted milliseconds still fail
+    for offset in 0..2 {
+        park(offset, max_sequence);
+        assert!(matches!(
+            generator.try_generate(),
+            Err(SnowIDError::SequenceExhausted { .. })
+        ));
+    }
+
+    // From the third one on, the spare takes over for the rest of the millisecond
+    park(2, max_sequence);
+    let spare_ids: Vec<u64> = (0..=max_sequence)
+        .map(|_| generator.try_generate().unwrap())
+        .collect();
+    assert!(spare_ids
+        .iter()
+        .all(|&id| generator.extract.timestamp(id) == timestamp + 2
+            && generator.extract.node(id) == 9));
+    assert_eq!(
+        spare_ids.iter().collect::<HashSet<_>>().len(),
+        spare_ids.len()
+    );
+    assert!(generator.try_generate().is_err());
+
+    // Pressure subsides: back to the primary node
+    park(4, 5);
+    let id = generator.try_generate().unwrap();
+    assert_eq!(generator.extract.decompose(id), (timestamp + 4, 1, 6));
+    park(5, max_sequence);
+    assert!(generator.try_generate().is_err());
+}
+
+#[test]
+fn test_spare_nodes_validation() {
+    let config = SnowIDConfig::default();
+
+    let generator = SnowID::with_spare_nodes(1, &[2, 3], config).unwrap();
+    assert_eq!(generator.spare_nodes(), &[2, 3]);
+    assert!(SnowID::new(1).unwrap().spare_nodes().is_empty());
+
+    assert!(matches!(
+        SnowID::with_spare_nodes(1, &[1024], config),
+        Err(SnowIDError::InvalidNodeId { node_id: 1024, .. })
+    ));
+    assert!(matches!(
+        SnowID::with_spare_nodes(1, &[2, 2], config),
+        Err(SnowIDError::InvalidLayout { .. })
+    ));
+    assert!(matches!(
+        SnowID::with_spare_nodes(1, &[1], config),
+        Err(SnowIDError::InvalidLayout { .. })
+    ));
+
+    // Every other node ID can be a spare
+    let all: Vec<u16> = (1..=config.max_node_id()).collect();
+    assert!(SnowID::with_spare_nodes(0, &all, config).is_ok());
+}
@@ -0,0 +1,105 @@
+use crate::*;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+type Fields = Vec<(&'static str, String)>;
+
+/// Minimal subscriber collecting the fields of every event
+#[derive(Clone, Default)]
+struct EventRecorder {
+    events: Arc<Mutex<Vec<Fields>>>,
+}
+
+struct FieldVisitor(Fields);
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push((field.name(), format!("{:?}", value)));
+    }
+}
+
+impl Subscriber for EventRecorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = FieldVisitor(Vec::new());
+        event.record(&mut visitor);
+        self.events.lock().unwrap().push(visitor.0);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+impl EventRecorder {
+    fn with_message(&self, message: &str) -> Vec<Fields> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|fields| fields.contains(&("message", message.to_string())))
+            .cloned()
+            .collect()
+    }
+}
+
+#[test]
+fn test_sequence_exhaustion_event() {
+    let recorder = EventRecorder::default();
+    let generator = SnowID::new(7).unwrap();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let timestamp = generator.extract.timestamp(generator.generate()) + 2;
+        generator.store_state(timestamp, generator.config.max_sequence_id());
+        generator.generate();
+    });
+
+    let events = recorder.with_message("sequence exhausted");
+    assert!(!events.is_empty());
+    assert!(events[0].contains(&("node_id", "7".to_string())));
+    assert!(events[0].iter().any(|(name, _)| *name == "timestamp"));
+    assert!(events[0].iter().any(|(name, _)| *name == "sequence"));
+}
+
+#[test]
+fn test_clock_regression_event() {
+    let recorder = EventRecorder::default();
+    let generator = SnowID::new(7).unwrap();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let timestamp = generator.extract.timestamp(generator.generate());
+        generator.store_state(timestamp + 1_000, 0);
+        generator.generate();
+    });
+
+    let events = recorder.with_message("clock moved backwards");
+    assert_eq!(events.len(), 1);
+    assert!(events[0].contains(&("node_id", "7".to_string())));
+    assert!(events[0].iter().any(|(name, _)| *name == "last_timestamp"));
+}
+
+#[test]
+fn test_no_events_on_success_path() {
+    let recorder = EventRecorder::default();
+    let generator = SnowID::new(7).unwrap();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        generator.generate();
+    });
+
+    assert!(recorder.events.lock().unwrap().is_empty());
+}